@@ -0,0 +1,58 @@
+use serde::{Serialize, Deserialize};
+
+use crate::Color;
+
+/// avatars are `AVATAR_SIZE`×`AVATAR_SIZE` pixels
+pub const AVATAR_SIZE: usize = 16;
+
+/// colors an avatar may use, so that a pixel fits in 4 bits
+pub const AVATAR_COLORS: usize = 16;
+
+/// a small picture drawn by the player
+///
+/// `pixels` holds two pixels per byte, column by column like `FigureLocal`,
+/// the lower 4 bits being the first one, each an index into `palette`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Avatar {
+    pub palette: Vec<Color>,
+    pub pixels: Vec<u8>,
+}
+
+impl Avatar {
+    /// `None` if the picture has more than `AVATAR_COLORS` colors
+    pub fn encode(data: &[[Color; AVATAR_SIZE]; AVATAR_SIZE]) -> Option<Self> {
+        let mut palette: Vec<Color> = Vec::new();
+        let mut pixels = vec![0u8; AVATAR_SIZE * AVATAR_SIZE / 2];
+        for (i, color) in data.iter().flatten().enumerate() {
+            let idx = match palette.iter().position(|c|c == color) {
+                Some(idx) => idx,
+                None if palette.len() < AVATAR_COLORS => {
+                    palette.push(*color);
+                    palette.len() - 1
+                },
+                None => return None,
+            };
+            pixels[i / 2] |= (idx as u8) << (4 * (i % 2));
+        }
+        Some(Self { palette, pixels })
+    }
+
+    /// pixels missing from a malformed avatar are white
+    pub fn pixel(&self, x: usize, y: usize) -> Color {
+        let i = x * AVATAR_SIZE + y;
+        self.pixels.get(i / 2)
+            .and_then(|byte|self.palette.get(((byte >> (4 * (i % 2))) & 0x0f) as usize))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn decode(&self) -> [[Color; AVATAR_SIZE]; AVATAR_SIZE] {
+        let mut data = [[Color::white(); AVATAR_SIZE]; AVATAR_SIZE];
+        for (x, col) in data.iter_mut().enumerate() {
+            for (y, pixel) in col.iter_mut().enumerate() {
+                *pixel = self.pixel(x, y);
+            }
+        }
+        data
+    }
+}
@@ -0,0 +1,48 @@
+use serde::{Serialize, Deserialize};
+
+use crate::{Frame, FigureLocal};
+
+/// what a player does in one step of a telephone game
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ChainTask {
+    /// the first step, write something to draw
+    Prompt,
+    /// draw what the previous player wrote
+    Draw {
+        prompt: String,
+    },
+    /// tell what the previous player drew
+    Describe {
+        drawing: Vec<Frame>,
+    },
+}
+
+/// what one player added to a chain
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ChainEntry {
+    Text(String),
+    /// the frames as they were drawn, so the drawing can be replayed
+    Drawing(Vec<Frame>),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChainLink {
+    pub author: u8,
+    pub entry: ChainEntry,
+}
+
+/// everything that became of the prompt of player `owner`, in the order of the steps
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Chain {
+    pub owner: u8,
+    pub links: Vec<ChainLink>,
+}
+
+/// the finished drawing of `frames`
+pub fn replay(frames: &[Frame]) -> FigureLocal {
+    let mut figure = FigureLocal::blank();
+    for frame in frames {
+        figure.render_frame(frame);
+    }
+    figure
+}
@@ -0,0 +1,46 @@
+use serde::{Serialize, Deserialize};
+
+/// how a drawing turns into points
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Scoring {
+    /// up, neutral and down votes counted into `PlayerState::score`
+    Votes,
+    /// average of the 1 to 5 star ratings
+    Stars,
+}
+
+/// the rules a game is played by
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum GameMode {
+    /// one drawer at a time, everyone else guesses
+    Classic,
+    /// everyone writes a prompt, then the chains go around alternating drawing and describing
+    Telephone,
+    /// the guessers see a drawing piece by piece and score more the earlier they guess it
+    Reveal,
+}
+
+/// settings of a room, only the host may change them
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RoomConfig {
+    /// times everyone gets to draw
+    pub rounds: u8,
+    /// seconds for each turn
+    pub turn_time: u8,
+    pub max_players: u8,
+    pub scoring: Scoring,
+    /// number of teams the players are split into, 0 for everyone on their own
+    pub teams: u8,
+    pub mode: GameMode,
+}
+
+/// one setting the host asks to change
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ConfigChange {
+    Rounds(u8),
+    TurnTime(u8),
+    MaxPlayers(u8),
+    Scoring(Scoring),
+    Teams(u8),
+    Mode(GameMode),
+}
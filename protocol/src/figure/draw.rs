@@ -5,7 +5,7 @@ use super::Color;
 /// 
 /// [reference](http://rosettacode.org/wiki/Bitmap/Bresenham%27s_line_algorithm)
 /// 
-pub fn line(data: &mut Data, c0:(u8,u8), c1:(u8,u8), color: Color) {
+pub fn line<const W: usize, const H: usize>(data: &mut Data<W, H>, c0:(u8,u8), c1:(u8,u8), color: Color) {
     let (x0,y0) = c0;
     let (x1,y1) = c1;
 
@@ -45,7 +45,7 @@ pub fn line(data: &mut Data, c0:(u8,u8), c1:(u8,u8), color: Color) {
     }
 }
 
-pub fn line_with_width(data: &mut Data, c0:(u8,u8), c1:(u8,u8), w:u8, color: Color) {
+pub fn line_with_width<const W: usize, const H: usize>(data: &mut Data<W, H>, c0:(u8,u8), c1:(u8,u8), w:u8, color: Color) {
     let (x0,y0) = c0;
     let (x1,y1) = c1;
 
@@ -105,13 +105,13 @@ pub fn line_with_width(data: &mut Data, c0:(u8,u8), c1:(u8,u8), w:u8, color: Col
 }
 
 #[inline]
-pub fn point(data: &mut Data, c:(u8,u8), color: Color) {
+pub fn point<const W: usize, const H: usize>(data: &mut Data<W, H>, c:(u8,u8), color: Color) {
     let (x,y) = c;
     data[x as usize][y as usize] = color;
 }
 
 #[inline]
-pub fn fill_square(data: &mut Data, c:(u8,u8), w:u8, color: Color) {
+pub fn fill_square<const W: usize, const H: usize>(data: &mut Data<W, H>, c:(u8,u8), w:u8, color: Color) {
     let left = (c.0 as i16) - ((w/2) as i16);
     let right = (c.0 as i16) + ((w/2) as i16);
     let top = (c.1 as i16) - ((w/2) as i16);
@@ -127,8 +127,8 @@ pub fn fill_square(data: &mut Data, c:(u8,u8), w:u8, color: Color) {
 /// this function will clamp x and y
 /// 
 #[inline]
-pub fn set_color_safe(data: &mut Data, x: i16, y:i16, color: Color) {
-    let y = y.clamp(0, 127);
-    let x = x.clamp(0, 127);
+pub fn set_color_safe<const W: usize, const H: usize>(data: &mut Data<W, H>, x: i16, y:i16, color: Color) {
+    let y = y.clamp(0, H as i16 - 1);
+    let x = x.clamp(0, W as i16 - 1);
     data[x as usize][y as usize] = color;
 }
\ No newline at end of file
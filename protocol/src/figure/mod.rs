@@ -1,21 +1,22 @@
 
 use serde::{Serialize, Deserialize};
-use crate::consts::*;
+use crate::{DRAWPAD_W, DRAWPAD_H};
 
 use super::frame::Frame;
 mod draw;
 #[macro_export] 
 macro_rules! rgb {
-    ($r:expr, $g:expr, $b:expr) => {crate::components::drawpad::Color::new($r as u8,$g as u8,$b as u8)};
+    ($r:expr, $g:expr, $b:expr) => {$crate::Color::new($r as u8,$g as u8,$b as u8)};
 }
 
-type Data = [[Color; DRAWPAD_H]; DRAWPAD_W];
+/// pixels by column, `data[x][y]`
+type Data<const W: usize, const H: usize> = [[Color; H]; W];
 #[inline]
-fn clear(data:&mut Data) {
+fn clear<const W: usize, const H: usize>(data:&mut Data<W, H>) {
     data.iter_mut().for_each(|col|col.fill(Color::white()));
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Color {pub r:u8,pub g:u8,pub b:u8}
 impl Color {
     pub const fn new(r:u8,g:u8,b:u8) -> Self{Self{
@@ -40,7 +41,7 @@ pub enum Tool {
 }
 
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum Instruction {
     PointerDown((u8, u8)),
     PointerMove((u8, u8)),
@@ -67,17 +68,17 @@ pub enum Operation {
 }
 
 impl Operation {
-    fn render(&self, data:&mut Data) {
+    fn render<const W: usize, const H: usize>(&self, data:&mut Data<W, H>) {
         match self {
             Operation::Pencil { path, color } => {
-                if path.len() == 0 {unreachable!();}
+                if path.is_empty() {unreachable!();}
                 else if path.len() == 1 {draw::point(data, path[0], *color)}
                 else {
                     path.windows(2).for_each(|segment|draw::line(data,segment[0], segment[1], *color))
                 }
             },
             Operation::Eraser { path, size } => {
-                if path.len() == 0 {unreachable!();}
+                if path.is_empty() {unreachable!();}
                 else if path.len() == 1 {draw::fill_square(data, path[0], *size, Color::white())}
                 else {
                     path.windows(2).for_each(|segment|draw::line_with_width(data,segment[0], segment[1], *size, Color::white()))
@@ -127,7 +128,7 @@ impl History {
         self.stack.clear();
     }
 
-    pub fn render(&mut self, data:&mut Data) {
+    pub fn render<const W: usize, const H: usize>(&mut self, data:&mut Data<W, H>) {
         if self.been_rendered > self.should_render {
             clear(data);
             self.been_rendered = 0;
@@ -140,8 +141,9 @@ impl History {
     }
 }
 
-pub struct FigureLocal {
-    data: Data,
+/// a picture of `W`×`H` pixels built from instructions, the drawpad by default
+pub struct FigureLocal<const W: usize = DRAWPAD_W, const H: usize = DRAWPAD_H> {
+    data: Data<W, H>,
     path: Option<Vec<(u8,u8)>>,
     history: History,
     color: Color,
@@ -151,14 +153,22 @@ pub struct FigureLocal {
 
 
 impl FigureLocal {
+    /// a blank drawpad
     pub fn blank() -> Self {
+        Self::new()
+    }
+}
+
+impl<const W: usize, const H: usize> FigureLocal<W, H> {
+    pub fn new() -> Self {
         FigureLocal {
-            data: [[rgb!(0xff,0xff,0xff); DRAWPAD_H]; DRAWPAD_W],
+            data: [[rgb!(0xff,0xff,0xff); H]; W],
             history: History::new(),
             path: None,
             color: rgb!(0,0,0),
             tool: Tool::Pencil,
-            size: 16
+            // 16 on the drawpad
+            size: (W / 8).max(1) as u8,
         }
     }
 
@@ -177,7 +187,9 @@ impl FigureLocal {
                 self.path = Some(path);
             },
             Instruction::PointerMove(coor) => {
-                self.path.as_mut().map(|p|p.push(*coor));
+                if let Some(p) = self.path.as_mut() {
+                    p.push(*coor);
+                }
             },
             Instruction::PointerUp(coor) => {
                 if let Some(mut path) = self.path.take() {
@@ -189,15 +201,15 @@ impl FigureLocal {
                         },
                         Tool::Pencil => Operation::Pencil{
                             path, 
-                            color: self.color.clone(), 
+                            color: self.color, 
                         },
                     };
                     
                     self.history.push(operation);
                 }
             },
-            Instruction::SetColor(c) => self.color=*c ,
-            Instruction::SetTool(t) => self.tool=*t,
+            Instruction::SetColor(c) => self.color = *c,
+            Instruction::SetTool(t) => self.tool = *t,
             Instruction::Clear => {
                 clear(&mut self.data);
                 self.history.push(Operation::Clear);
@@ -228,7 +240,7 @@ impl FigureLocal {
                 },
                 Tool::Pencil => Operation::Pencil{
                     path, 
-                    color: self.color.clone(), 
+                    color: self.color, 
                 },
             };
             operation.render(&mut self.data);
@@ -236,11 +248,17 @@ impl FigureLocal {
     }
 
     #[inline]
-    pub fn get_ref(&self) -> &Data {
+    pub fn get_ref(&self) -> &Data<W, H> {
         &self.data
     }
 }
 
+impl<const W: usize, const H: usize> Default for FigureLocal<W, H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 pub trait Figure {
     fn excute(&mut self, ins: &Instruction);
 
@@ -1,11 +1,17 @@
 use serde::{Serialize, Deserialize};
 use super::figure::Instruction;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Frame {
     pub instructions: Vec<Instruction>
 }
 
+impl Default for Frame {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Frame {
     pub fn new()->Self{
         Self{
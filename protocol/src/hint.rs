@@ -0,0 +1,29 @@
+use serde::{Serialize, Deserialize};
+
+/// one character of the topic, as the guessers see it
+///
+/// every `char` is a slot, so a CJK word has one slot per character just like a latin word
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum HintSlot {
+    /// a character yet to be guessed
+    Blank,
+    /// whitespace between words, never hidden
+    Gap,
+    /// a character the server has revealed
+    Letter(char),
+}
+
+impl HintSlot {
+    /// masks `word`, revealing the characters at the indices in `revealed`
+    pub fn mask(word: &str, revealed: &[usize]) -> Vec<HintSlot> {
+        word.chars().enumerate().map(|(idx, c)| {
+            if c.is_whitespace() {
+                HintSlot::Gap
+            } else if revealed.contains(&idx) {
+                HintSlot::Letter(c)
+            } else {
+                HintSlot::Blank
+            }
+        }).collect()
+    }
+}
@@ -0,0 +1,29 @@
+//! the contract between dng-yew and dng-server
+//!
+//! every type here is bincode-encoded on the wire, where enum variants are identified
+//! by their index, so new variants go to the end and anything else bumps `PROTOCOL_VERSION`
+
+mod request;
+mod response;
+mod frame;
+mod figure;
+mod version;
+mod hint;
+mod avatar;
+mod config;
+mod chain;
+mod reveal;
+
+pub use request::PlayerRequest;
+pub use response::{PlayerResponse, PlayerState, Stage, RoomState};
+pub use frame::Frame;
+pub use figure::{Color, Tool, Instruction, FigureLocal, Figure};
+pub use version::*;
+pub use hint::HintSlot;
+pub use avatar::{Avatar, AVATAR_SIZE, AVATAR_COLORS};
+pub use config::{RoomConfig, ConfigChange, Scoring, GameMode};
+pub use chain::{ChainTask, ChainEntry, ChainLink, Chain, replay};
+pub use reveal::{RevealStyle, RevealSchedule, REVEAL_TILES};
+
+pub const DRAWPAD_W: usize = 128;
+pub const DRAWPAD_H: usize = 128;
@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Avatar, ConfigChange, Frame};
+
+#[derive(Deserialize, Serialize, PartialEq, Debug, Clone)]
+pub enum PlayerRequest {
+    SetName {
+        name: String,
+    }, 
+    Chat {
+        msg: String,
+    },
+    ImReady,
+    ImUnready,
+    Chunk {
+        bin: Vec<u8>
+    },
+    Mark {
+        score: i8,
+    },
+    Lexicon(Vec<String>),
+    LexiconService(u32),
+    LexiconGit(String),
+    Ping {
+        stamp: u32
+    },
+    /// first message of every connection
+    Hello {
+        version: u16,
+        capabilities: u32,
+    },
+    /// answer to `PlayerResponse::TopicChoices`, index of the picked candidate
+    ChooseTopic {
+        idx: u8,
+    },
+    /// rating of the drawing from 1 to 5 stars, a later one replaces the earlier until the poll closes
+    Rate {
+        stars: u8,
+    },
+    SetAvatar(Avatar),
+    /// host only
+    Config(ConfigChange),
+    /// host only, removes player `target` from the room
+    Kick {
+        target: u8,
+    },
+    /// asks everyone whether to remove player `target`
+    VoteKick {
+        target: u8,
+    },
+    /// answer to `PlayerResponse::VoteKick`, may be changed until it ends
+    KickBallot {
+        target: u8,
+        agree: bool,
+    },
+    /// chat for the team of the sender only
+    TeamChat {
+        msg: String,
+    },
+    /// the prompt or the description for `PlayerResponse::ChainStep`, a later one replaces the earlier
+    ChainText {
+        text: String,
+    },
+    /// the drawing for `PlayerResponse::ChainStep`, sent when the step ends or the player is done
+    ChainDrawing {
+        frames: Vec<Frame>,
+    },
+}
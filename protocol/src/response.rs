@@ -0,0 +1,151 @@
+
+use serde::{Serialize, Deserialize};
+
+use crate::{HintSlot, Avatar, RoomConfig, ChainTask, Chain, Frame, RevealSchedule};
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PlayerState {
+    pub name: String,
+    pub idx: u8,
+    pub ready: bool,
+    pub score: [u8;3],
+    pub drawpoint: u8,
+    pub timepoint: u16,
+    /// `None` unless the room plays in teams
+    pub team: Option<u8>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum PlayerResponse {
+    Chat {
+        sender: String,
+        msg: String
+    },
+    Notice {
+        msg: String
+    },
+    Warn {
+        msg: String
+    },
+    GameStart,
+    GameEnd,
+    Topic {
+        topic_word: String
+    },
+    Chunk {
+        bin: Vec<u8>
+    },
+
+    /// in team mode every team gets the index of its own drawer
+    TurnStart(u8),
+    TurnEnd,
+    MarkStart,
+    Poll,
+    MarkEnd,
+
+    PlayerStates (Vec<PlayerState>),
+    CountDown(u8),
+
+    RoomFullfilled,
+
+    Pong {
+        stamp: u32
+    },
+    /// answer to `PlayerRequest::Hello`
+    Hello {
+        version: u16,
+        capabilities: u32,
+    },
+    RoomState(RoomState),
+    /// the topic as the guessers may see it, sent again whenever a letter is revealed
+    Hint {
+        slots: Vec<HintSlot>,
+    },
+    /// the drawer picks one of `candidates` within `timeout` seconds, or gets the first one
+    TopicChoices {
+        candidates: Vec<String>,
+        timeout: u8,
+    },
+    /// sent when the poll closes, `stars[n]` is the number of players who gave `n+1` stars
+    MarkResult {
+        drawer: u8,
+        stars: [u8; 5],
+    },
+    /// the avatar of player `idx`, sent when it changes and to players who join
+    Avatar {
+        idx: u8,
+        avatar: Avatar,
+    },
+    /// index of the player receiving it, sent once after joining
+    Seat(u8),
+    /// index of the host, sent after joining and whenever the host leaves
+    Host(u8),
+    /// sent after joining and whenever the host changes it
+    RoomConfig(RoomConfig),
+    /// `initiator` started a vote to remove `target`
+    VoteKick {
+        target: u8,
+        initiator: u8,
+    },
+    VoteKickEnd {
+        target: u8,
+        kicked: bool,
+    },
+    /// player `idx` guessed the topic, `order` counts from 1
+    Guessed {
+        idx: u8,
+        order: u8,
+        /// seconds since the turn started
+        elapsed: u16,
+        points: u8,
+    },
+    /// chat only the team of the sender gets
+    TeamChat {
+        sender: String,
+        msg: String,
+    },
+    /// telephone mode, `step` of `steps` starts, counting from 0
+    ChainStep {
+        step: u8,
+        steps: u8,
+        task: ChainTask,
+        /// seconds to finish the step
+        timeout: u8,
+    },
+    /// telephone mode, whatever is not sent yet should be sent now
+    ChainStepEnd,
+    /// telephone mode, every chain once all the steps are done
+    ChainReveal {
+        chains: Vec<Chain>,
+    },
+    /// reveal mode, the drawing of this turn, either the finished one of the drawer or one from the gallery
+    Reveal {
+        frames: Vec<Frame>,
+        schedule: RevealSchedule,
+    },
+    /// reveal mode, show the drawing up to this step of `RevealSchedule`
+    RevealStep(u8),
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Stage {
+    Unready,
+    Ready,
+    /// index of the drawer
+    Drawing(u8),
+    /// index of the drawer whose drawing is marked
+    Marking(u8),
+    Over,
+    /// telephone mode, the step everyone is at
+    Chain(u8),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RoomState {
+    pub name: Option<String>,
+    pub stage: Stage,
+    pub playercount: u8,
+    /// the lexicon was uploaded by a player, rather than picked from the lexicon server
+    pub user_lexicon: bool,
+    /// code of the lexicon on the lexicon server
+    pub lexicon: u32,
+}
\ No newline at end of file
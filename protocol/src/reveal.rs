@@ -0,0 +1,69 @@
+use std::ops::Range;
+
+use serde::{Serialize, Deserialize};
+
+/// tiles along each side of the drawpad, for `RevealStyle::Mask`
+pub const REVEAL_TILES: usize = 8;
+
+/// how a drawing is shown piece by piece
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum RevealStyle {
+    /// the finished drawing behind tiles which are taken away in a shuffled order
+    Mask,
+    /// the strokes played back in the order they were drawn
+    Replay,
+}
+
+/// sent along with the drawing, the server then counts the steps up with `PlayerResponse::RevealStep`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RevealSchedule {
+    pub style: RevealStyle,
+    /// the whole drawing is shown at the last step
+    pub steps: u8,
+    /// seconds between two steps
+    pub interval: u8,
+    /// shuffles the tiles, so every client takes them away in the same order
+    pub seed: u32,
+    /// points for guessing the topic at each step, from step 0
+    pub points: Vec<u8>,
+}
+
+impl RevealSchedule {
+    /// indices of all the tiles, column-major, in the order they are revealed
+    pub fn order(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..REVEAL_TILES * REVEAL_TILES).collect();
+        // xorshift32, which never leaves 0
+        let mut x = self.seed.max(1);
+        for i in (1..order.len()).rev() {
+            x ^= x << 13;
+            x ^= x >> 17;
+            x ^= x << 5;
+            order.swap(i, x as usize % (i + 1));
+        }
+        order
+    }
+
+    /// for every tile whether it is still hidden at `step`
+    pub fn mask(&self, step: u8) -> [bool; REVEAL_TILES * REVEAL_TILES] {
+        let mut mask = [true; REVEAL_TILES * REVEAL_TILES];
+        let revealed = self.part(REVEAL_TILES * REVEAL_TILES, step).end;
+        for tile in &self.order()[..revealed] {
+            mask[*tile] = false;
+        }
+        mask
+    }
+
+    /// the part of `len` items shown first at `step`, the whole of them once all the steps are done
+    pub fn part(&self, len: usize, step: u8) -> Range<usize> {
+        let until = |step: u8| match self.steps {
+            0 => len,
+            steps => len * step.min(steps) as usize / steps as usize,
+        };
+        until(step.saturating_sub(1))..until(step)
+    }
+
+    /// points for guessing it at `step`, the last entry holds for the steps after
+    pub fn points_at(&self, step: u8) -> u8 {
+        self.points.get(step as usize).or_else(|| self.points.last()).copied().unwrap_or(0)
+    }
+}
@@ -0,0 +1,13 @@
+/// bumped whenever the wire format changes in a way older peers can not decode,
+/// which is anything but appending a new variant to `PlayerRequest` or `PlayerResponse`
+pub const PROTOCOL_VERSION: u16 = 4;
+
+/// the server answers pings
+pub const CAP_HEARTBEAT: u32 = 1 << 0;
+
+/// messages other than chunks may be sent as json in text frames,
+/// once both sides announce it the client switches its requests over
+pub const CAP_JSON: u32 = 1 << 1;
+
+/// the server takes `PlayerRequest::Rate`, otherwise ratings are sent as `PlayerRequest::Mark`
+pub const CAP_STARS: u32 = 1 << 2;
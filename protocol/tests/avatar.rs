@@ -0,0 +1,52 @@
+use dng_protocol::*;
+
+fn smiley() -> [[Color; AVATAR_SIZE]; AVATAR_SIZE] {
+    let mut data = [[Color::white(); AVATAR_SIZE]; AVATAR_SIZE];
+    data[4][4] = Color::new(0, 0, 0);
+    data[11][4] = Color::new(0, 0, 0);
+    for col in &mut data[4..12] {
+        col[11] = Color::new(0xdd, 0x66, 0x66);
+    }
+    data
+}
+
+#[test]
+fn round_trip() {
+    let avatar = Avatar::encode(&smiley()).unwrap();
+    assert_eq!(avatar.palette.len(), 3);
+    assert_eq!(avatar.pixels.len(), AVATAR_SIZE * AVATAR_SIZE / 2);
+    assert_eq!(avatar.decode(), smiley());
+}
+
+#[test]
+fn too_many_colors() {
+    let mut data = [[Color::white(); AVATAR_SIZE]; AVATAR_SIZE];
+    for (x, col) in data.iter_mut().enumerate() {
+        col[0] = Color::new(x as u8, 0, 0);
+    }
+    assert_eq!(Avatar::encode(&data), None);
+}
+
+#[test]
+fn malformed_is_white() {
+    let avatar = Avatar { palette: vec![], pixels: vec![0x11] };
+    assert_eq!(avatar.pixel(0, 0), Color::white());
+    assert_eq!(avatar.pixel(15, 15), Color::white());
+}
+
+#[test]
+fn drawn_with_the_figure() {
+    let mut figure = FigureLocal::<AVATAR_SIZE, AVATAR_SIZE>::new();
+    figure.render_frame(&Frame { instructions: vec![
+        Instruction::SetColor(Color::new(0, 0, 0)),
+        Instruction::PointerDown((0, 0)),
+        Instruction::PointerUp((15, 15)),
+        // the eraser is clamped to the small figure
+        Instruction::SetTool(Tool::Eraser),
+        Instruction::PointerDown((15, 15)),
+        Instruction::PointerUp((15, 15)),
+    ]});
+    let avatar = Avatar::encode(figure.get_ref()).unwrap();
+    assert_eq!(avatar.pixel(7, 7), Color::new(0, 0, 0));
+    assert_eq!(avatar.pixel(15, 15), Color::white());
+}
@@ -0,0 +1,109 @@
+//! the renderer shared by the drawpad, run natively
+
+use dng_protocol::*;
+
+const BLACK: Color = Color::new(0, 0, 0);
+const RED: Color = Color::new(0xff, 0, 0);
+
+fn stroke(color: Color, tool: Tool, path: &[(u8, u8)]) -> Frame {
+    let mut instructions = vec![
+        Instruction::SetColor(color),
+        Instruction::SetTool(tool),
+        Instruction::PointerDown(path[0]),
+    ];
+    for coor in &path[1..path.len()-1] {
+        instructions.push(Instruction::PointerMove(*coor));
+    }
+    instructions.push(Instruction::PointerUp(path[path.len()-1]));
+    Frame { instructions }
+}
+
+fn single(ins: Instruction) -> Frame {
+    Frame { instructions: vec![ins] }
+}
+
+fn pixel(figure: &FigureLocal, x: usize, y: usize) -> Color {
+    figure.get_ref()[x][y]
+}
+
+#[test]
+fn blank_is_white() {
+    let figure = FigureLocal::blank();
+    assert!(figure.get_ref().iter().flatten().all(|c|*c == Color::white()));
+}
+
+#[test]
+fn pencil_draws_a_line() {
+    let mut figure = FigureLocal::blank();
+    figure.render_frame(&stroke(RED, Tool::Pencil, &[(10, 10), (20, 10)]));
+    for x in 10..=20 {
+        assert_eq!(pixel(&figure, x, 10), RED);
+    }
+    assert_eq!(pixel(&figure, 21, 10), Color::white());
+    assert_eq!(pixel(&figure, 15, 11), Color::white());
+}
+
+#[test]
+fn stroke_in_progress_is_shown() {
+    let mut figure = FigureLocal::blank();
+    figure.render_frame(&Frame { instructions: vec![
+        Instruction::SetColor(RED),
+        Instruction::SetTool(Tool::Pencil),
+        Instruction::PointerDown((5, 5)),
+        Instruction::PointerMove((5, 9)),
+    ]});
+    assert_eq!(pixel(&figure, 5, 7), RED);
+}
+
+#[test]
+fn eraser_whitens_a_square() {
+    let mut figure = FigureLocal::blank();
+    figure.render_frame(&stroke(BLACK, Tool::Pencil, &[(0, 64), (127, 64)]));
+    figure.render_frame(&stroke(BLACK, Tool::Eraser, &[(64, 64), (64, 64)]));
+    assert_eq!(pixel(&figure, 64, 64), Color::white());
+    assert_eq!(pixel(&figure, 60, 64), Color::white());
+    assert_eq!(pixel(&figure, 10, 64), BLACK);
+}
+
+#[test]
+fn undo_and_redo() {
+    let mut figure = FigureLocal::blank();
+    figure.render_frame(&stroke(RED, Tool::Pencil, &[(1, 1), (1, 5)]));
+    figure.render_frame(&single(Instruction::Undo));
+    assert_eq!(pixel(&figure, 1, 3), Color::white());
+    figure.render_frame(&single(Instruction::Redo));
+    assert_eq!(pixel(&figure, 1, 3), RED);
+}
+
+#[test]
+fn clear_is_undoable_and_reset_is_not() {
+    let mut figure = FigureLocal::blank();
+    figure.render_frame(&stroke(RED, Tool::Pencil, &[(1, 1), (1, 5)]));
+    figure.render_frame(&single(Instruction::Clear));
+    assert_eq!(pixel(&figure, 1, 3), Color::white());
+    figure.render_frame(&single(Instruction::Undo));
+    assert_eq!(pixel(&figure, 1, 3), RED);
+
+    figure.render_frame(&single(Instruction::Reset));
+    figure.render_frame(&single(Instruction::Redo));
+    figure.render_frame(&single(Instruction::Undo));
+    assert_eq!(pixel(&figure, 1, 3), Color::white());
+}
+
+#[test]
+fn replay_renders_every_frame() {
+    let frames = [
+        stroke(RED, Tool::Pencil, &[(10, 10), (20, 10)]),
+        stroke(BLACK, Tool::Pencil, &[(10, 30), (20, 30)]),
+        single(Instruction::Undo),
+    ];
+    let mut figure = FigureLocal::blank();
+    for frame in &frames {
+        figure.render_frame(frame);
+    }
+    let replayed = replay(&frames);
+    assert_eq!(replayed.get_ref(), figure.get_ref());
+    assert_eq!(pixel(&replayed, 15, 10), RED);
+    assert_eq!(pixel(&replayed, 15, 30), Color::white());
+    assert_eq!(pixel(&replay(&[]), 15, 10), Color::white());
+}
@@ -0,0 +1,14 @@
+use dng_protocol::HintSlot::{self, *};
+
+#[test]
+fn latin() {
+    assert_eq!(HintSlot::mask("ice cream", &[0, 5]), vec![
+        Letter('i'), Blank, Blank, Gap, Blank, Letter('r'), Blank, Blank, Blank,
+    ]);
+}
+
+#[test]
+fn cjk() {
+    assert_eq!(HintSlot::mask("画蛇添足", &[]), vec![Blank; 4]);
+    assert_eq!(HintSlot::mask("画蛇添足", &[3]), vec![Blank, Blank, Blank, Letter('足')]);
+}
@@ -0,0 +1,56 @@
+use dng_protocol::*;
+
+fn schedule(steps: u8) -> RevealSchedule {
+    RevealSchedule {
+        style: RevealStyle::Mask,
+        steps,
+        interval: 10,
+        seed: 42,
+        points: vec![10, 8, 6],
+    }
+}
+
+#[test]
+fn order_is_a_shuffle() {
+    let order = schedule(4).order();
+    let mut sorted = order.clone();
+    sorted.sort_unstable();
+    assert_eq!(sorted, (0..REVEAL_TILES * REVEAL_TILES).collect::<Vec<_>>());
+    assert_ne!(order, sorted);
+    assert_eq!(order, schedule(4).order());
+    assert_ne!(order, RevealSchedule { seed: 43, ..schedule(4) }.order());
+}
+
+#[test]
+fn mask_shrinks_step_by_step() {
+    let schedule = schedule(4);
+    let hidden = |step| schedule.mask(step).iter().filter(|h|**h).count();
+    assert_eq!(hidden(0), 64);
+    assert_eq!(hidden(1), 48);
+    assert_eq!(hidden(3), 16);
+    assert_eq!(hidden(4), 0);
+    assert_eq!(hidden(9), 0);
+    // a tile once revealed stays so
+    let (early, late) = (schedule.mask(1), schedule.mask(2));
+    assert!(early.iter().zip(late).all(|(e, l)| *e || !l));
+}
+
+#[test]
+fn parts_cover_everything_once() {
+    let schedule = schedule(3);
+    assert_eq!(schedule.part(10, 0), 0..0);
+    assert_eq!(schedule.part(10, 1), 0..3);
+    assert_eq!(schedule.part(10, 2), 3..6);
+    assert_eq!(schedule.part(10, 3), 6..10);
+    assert_eq!(schedule.part(10, 4), 10..10);
+    assert_eq!(RevealSchedule { steps: 0, ..schedule }.part(10, 0), 10..10);
+}
+
+#[test]
+fn earlier_guesses_score_more() {
+    let schedule = schedule(4);
+    assert_eq!(schedule.points_at(0), 10);
+    assert_eq!(schedule.points_at(2), 6);
+    assert_eq!(schedule.points_at(4), 6);
+    assert_eq!(RevealSchedule { points: vec![], ..schedule }.points_at(1), 0);
+}
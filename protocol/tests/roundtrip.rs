@@ -0,0 +1,182 @@
+//! every message goes through bincode and json and back unchanged, and encodes to the bytes
+//! recorded in `tests/fixtures`, so a reordered variant or field is caught here
+//! rather than by a server dropping messages
+//!
+//! after a deliberate wire change, bump `PROTOCOL_VERSION` and rerun with
+//! `DNG_BLESS=1 cargo test -p dng-protocol` to record the new fixtures
+
+use std::{fmt::Debug, fs, path::PathBuf};
+
+use bincode::{serialize, deserialize};
+use serde::{Serialize, de::DeserializeOwned};
+use dng_protocol::*;
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(format!("{}.bin", name))
+}
+
+fn check<T>(name: &str, msg: T) 
+where T: Serialize + DeserializeOwned + PartialEq + Debug
+{
+    let bin = serialize(&msg).unwrap();
+    assert_eq!(deserialize::<T>(&bin).unwrap(), msg, "{} does not round-trip", name);
+    let json = serde_json::to_string(&msg).unwrap();
+    assert_eq!(serde_json::from_str::<T>(&json).unwrap(), msg, "{} does not round-trip as json", name);
+
+    let path = fixture(name);
+    if std::env::var_os("DNG_BLESS").is_some() {
+        fs::write(&path, &bin).unwrap();
+    } else {
+        let expected = fs::read(&path).unwrap_or_else(|_|panic!("missing fixture {:?}", path));
+        assert_eq!(bin, expected, "{} no longer encodes like {:?}", name, path);
+    }
+}
+
+fn avatar() -> Avatar {
+    let mut data = [[Color::white(); AVATAR_SIZE]; AVATAR_SIZE];
+    data[3][5] = Color::new(0xdd, 0x66, 0x66);
+    Avatar::encode(&data).unwrap()
+}
+
+fn player() -> PlayerState {
+    PlayerState {
+        name: "画家".into(),
+        idx: 3,
+        ready: true,
+        score: [1, 2, 3],
+        drawpoint: 40,
+        timepoint: 600,
+        team: Some(1),
+    }
+}
+
+fn schedule() -> RevealSchedule {
+    RevealSchedule {
+        style: RevealStyle::Mask,
+        steps: 4,
+        interval: 10,
+        seed: 0x1a2b3c4f,
+        points: vec![10, 8, 6, 4, 2],
+    }
+}
+
+fn chunk() -> Vec<Frame> {
+    vec![
+        Frame { instructions: vec![
+            Instruction::SetColor(Color::new(0xdd, 0x66, 0x66)),
+            Instruction::SetTool(Tool::Pencil),
+            Instruction::PointerDown((1, 2)),
+        ]},
+        Frame { instructions: vec![Instruction::PointerMove((3, 4))] },
+        Frame { instructions: vec![
+            Instruction::PointerUp((5, 6)),
+            Instruction::SetTool(Tool::Eraser),
+            Instruction::Undo,
+            Instruction::Redo,
+            Instruction::Clear,
+            Instruction::Reset,
+        ]},
+    ]
+}
+
+#[test]
+fn requests() {
+    use PlayerRequest::*;
+    check("req_set_name", SetName { name: "画家".into() });
+    check("req_chat", Chat { msg: "hello".into() });
+    check("req_im_ready", ImReady);
+    check("req_im_unready", ImUnready);
+    check("req_chunk", Chunk { bin: serialize(&chunk()).unwrap() });
+    check("req_mark", Mark { score: -1 });
+    check("req_lexicon", Lexicon(vec!["苹果".into(), "apple".into()]));
+    check("req_lexicon_service", LexiconService(0x1a2b3c4f));
+    check("req_lexicon_git", LexiconGit("https://github.com/4t145/dng-lex/blob/main/chengyu.json".into()));
+    check("req_ping", Ping { stamp: 5000 });
+    check("req_hello", Hello { version: PROTOCOL_VERSION, capabilities: CAP_HEARTBEAT });
+    check("req_choose_topic", ChooseTopic { idx: 2 });
+    check("req_rate", Rate { stars: 4 });
+    check("req_set_avatar", SetAvatar(avatar()));
+    check("req_config", Config(ConfigChange::TurnTime(90)));
+    check("req_kick", Kick { target: 2 });
+    check("req_vote_kick", VoteKick { target: 2 });
+    check("req_kick_ballot", KickBallot { target: 2, agree: true });
+    check("req_team_chat", TeamChat { msg: "画的是我们的".into() });
+    check("req_chain_text", ChainText { text: "会飞的猫".into() });
+    check("req_chain_drawing", ChainDrawing { frames: chunk() });
+}
+
+#[test]
+fn responses() {
+    use PlayerResponse::*;
+    check("resp_chat", Chat { sender: "画家".into(), msg: "hello".into() });
+    check("resp_notice", Notice { msg: "notice".into() });
+    check("resp_warn", Warn { msg: "warn".into() });
+    check("resp_game_start", GameStart);
+    check("resp_game_end", GameEnd);
+    check("resp_topic", Topic { topic_word: "苹果".into() });
+    check("resp_chunk", Chunk { bin: serialize(&chunk()).unwrap() });
+    check("resp_turn_start", TurnStart(3));
+    check("resp_turn_end", TurnEnd);
+    check("resp_mark_start", MarkStart);
+    check("resp_poll", Poll);
+    check("resp_mark_end", MarkEnd);
+    check("resp_player_states", PlayerStates(vec![player()]));
+    check("resp_count_down", CountDown(59));
+    check("resp_room_fullfilled", RoomFullfilled);
+    check("resp_pong", Pong { stamp: 5000 });
+    check("resp_hello", Hello { version: PROTOCOL_VERSION, capabilities: CAP_HEARTBEAT });
+    check("resp_room_state", RoomState(dng_protocol::RoomState {
+        name: Some("房间".into()),
+        stage: Stage::Marking(2),
+        playercount: 5,
+        user_lexicon: false,
+        lexicon: 0x1a2b3c4f,
+    }));
+    check("resp_hint", Hint { slots: HintSlot::mask("画 a", &[2]) });
+    check("resp_topic_choices", TopicChoices { candidates: vec!["苹果".into(), "apple".into()], timeout: 10 });
+    check("resp_mark_result", MarkResult { drawer: 3, stars: [0, 1, 0, 2, 4] });
+    check("resp_avatar", PlayerResponse::Avatar { idx: 3, avatar: avatar() });
+    check("resp_seat", Seat(3));
+    check("resp_host", Host(0));
+    check("resp_room_config", RoomConfig(dng_protocol::RoomConfig {
+        rounds: 3,
+        turn_time: 90,
+        max_players: 8,
+        scoring: Scoring::Stars,
+        teams: 2,
+        mode: GameMode::Telephone,
+    }));
+    check("resp_vote_kick", VoteKick { target: 2, initiator: 0 });
+    check("resp_vote_kick_end", VoteKickEnd { target: 2, kicked: false });
+    check("resp_guessed", Guessed { idx: 1, order: 2, elapsed: 37, points: 5 });
+    check("resp_team_chat", TeamChat { sender: "画家".into(), msg: "好像是苹果".into() });
+    check("resp_chain_step", ChainStep { step: 1, steps: 4, task: ChainTask::Draw { prompt: "会飞的猫".into() }, timeout: 60 });
+    check("resp_chain_step_describe", ChainStep { step: 2, steps: 4, task: ChainTask::Describe { drawing: chunk() }, timeout: 30 });
+    check("resp_chain_step_end", ChainStepEnd);
+    check("resp_chain_reveal", ChainReveal { chains: vec![Chain { owner: 3, links: vec![
+        ChainLink { author: 3, entry: ChainEntry::Text("会飞的猫".into()) },
+        ChainLink { author: 0, entry: ChainEntry::Drawing(chunk()) },
+    ]}]});
+    check("resp_reveal", Reveal { frames: chunk(), schedule: schedule() });
+    check("resp_reveal_step", RevealStep(2));
+    check("resp_room_config_reveal", RoomConfig(dng_protocol::RoomConfig {
+        rounds: 2,
+        turn_time: 60,
+        max_players: 8,
+        scoring: Scoring::Votes,
+        teams: 0,
+        mode: GameMode::Reveal,
+    }));
+    check("resp_room_state_chain", RoomState(dng_protocol::RoomState {
+        name: None,
+        stage: Stage::Chain(2),
+        playercount: 4,
+        user_lexicon: true,
+        lexicon: 0,
+    }));
+}
+
+#[test]
+fn chunks() {
+    check("chunk", chunk());
+}
@@ -0,0 +1,188 @@
+use gloo_storage::{LocalStorage, Storage};
+use wasm_bindgen::{Clamped, JsCast};
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, ImageData, PointerEvent};
+use yew::{Component, Context, Html, html, NodeRef, Properties};
+use yew_agent::{Bridge, Bridged, Dispatcher, Dispatched};
+
+use dng_protocol::{Avatar, AVATAR_SIZE, Color, FigureLocal, Frame, Instruction, Tool};
+use super::drawpad::{DrawpadAgent, DrawpadReq};
+use super::console::{agent::ConsoleAgent, item::ItemKind};
+use crate::locals::Locals;
+use crate::ws::{Req, WsReqAgent};
+
+const STORAGE_KEY: &str = "dng-avatar";
+
+/// on-screen pixels per avatar pixel
+const SCALE: i32 = 8;
+
+/// the avatar saved by the editor in an earlier session
+pub fn cached() -> Option<Avatar> {
+    LocalStorage::get(STORAGE_KEY).ok()
+}
+
+pub enum AvatarMsg {
+    Pointer {
+        coor: (i32, i32),
+        down: bool,
+        buttons: u16,
+    },
+    Up,
+    Req(DrawpadReq),
+    Save,
+}
+
+#[derive(Debug, Properties, PartialEq)]
+pub struct AvatarEditorProps {
+    /// the localization of the console it is opened in
+    pub local: *const Locals<'static>,
+}
+
+/// a tiny drawpad for the avatar, taking the color and tool of the toolbar
+pub struct AvatarEditor {
+    figure: FigureLocal<AVATAR_SIZE, AVATAR_SIZE>,
+    color: Color,
+    tool: Tool,
+    /// last point of the stroke being drawn
+    stroke: Option<(u8, u8)>,
+    canvas_ref: NodeRef,
+    req_bus: Dispatcher<WsReqAgent>,
+    console: Dispatcher<ConsoleAgent>,
+    _drawpad_bus: Box<dyn Bridge<DrawpadAgent>>,
+}
+
+impl AvatarEditor {
+    fn coor(coor: (i32, i32)) -> (u8, u8) {
+        let max = AVATAR_SIZE as i32 - 1;
+        ((coor.0 / SCALE).clamp(0, max) as u8, (coor.1 / SCALE).clamp(0, max) as u8)
+    }
+
+    fn sync_figure(&self) {
+        let ctx = self.canvas_ref.cast::<HtmlCanvasElement>()
+            .and_then(|canvas|canvas.get_context("2d").ok().flatten())
+            .and_then(|ctx|ctx.dyn_into::<CanvasRenderingContext2d>().ok());
+        if let Some(ctx) = ctx {
+            let raw = self.figure.get_ref();
+            let mut data = Vec::with_capacity(AVATAR_SIZE * AVATAR_SIZE * 4);
+            for y in 0..AVATAR_SIZE {
+                for col in raw {
+                    let c = col[y];
+                    data.extend_from_slice(&[c.r, c.g, c.b, 0xff]);
+                }
+            }
+            if let Ok(image) = ImageData::new_with_u8_clamped_array(Clamped(&data), AVATAR_SIZE as u32) {
+                ctx.put_image_data(&image, 0.0, 0.0).unwrap_or_default();
+            }
+        }
+    }
+}
+
+impl Component for AvatarEditor {
+    type Message = AvatarMsg;
+    type Properties = AvatarEditorProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let mut figure = FigureLocal::new();
+        // start from the cached avatar, one dot per pixel
+        if let Some(avatar) = cached() {
+            let mut instructions = vec![Instruction::SetTool(Tool::Pencil)];
+            for x in 0..AVATAR_SIZE {
+                for y in 0..AVATAR_SIZE {
+                    let color = avatar.pixel(x, y);
+                    if color != Color::white() {
+                        let coor = (x as u8, y as u8);
+                        instructions.extend([Instruction::SetColor(color), Instruction::PointerDown(coor), Instruction::PointerUp(coor)]);
+                    }
+                }
+            }
+            figure.render_frame(&Frame { instructions });
+        }
+        Self {
+            figure,
+            color: Color::new(0, 0, 0),
+            tool: Tool::Pencil,
+            stroke: None,
+            canvas_ref: NodeRef::default(),
+            req_bus: WsReqAgent::dispatcher(),
+            console: ConsoleAgent::dispatcher(),
+            _drawpad_bus: DrawpadAgent::bridge(ctx.link().callback(AvatarMsg::Req)),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let instructions = match msg {
+            AvatarMsg::Pointer { coor, down: true, buttons } => {
+                // right button erases, as on the drawpad
+                let tool = match (self.tool, buttons) {
+                    (Tool::Pencil, 0b00001)|(Tool::Eraser, 0b00010) => Tool::Pencil,
+                    (Tool::Eraser, 0b00001)|(Tool::Pencil, 0b00010) => Tool::Eraser,
+                    _ => return false,
+                };
+                let coor = Self::coor(coor);
+                self.stroke = Some(coor);
+                vec![Instruction::SetColor(self.color), Instruction::SetTool(tool), Instruction::PointerDown(coor)]
+            },
+            AvatarMsg::Pointer { coor, down: false, .. } if self.stroke.is_some() => {
+                let coor = Self::coor(coor);
+                self.stroke = Some(coor);
+                vec![Instruction::PointerMove(coor)]
+            },
+            AvatarMsg::Pointer { .. } => return false,
+            AvatarMsg::Up => match self.stroke.take() {
+                Some(coor) => vec![Instruction::PointerUp(coor)],
+                None => return false,
+            },
+            AvatarMsg::Req(DrawpadReq::SetColor(color)) => {self.color = color; return false},
+            AvatarMsg::Req(DrawpadReq::SetTool(tool)) => {self.tool = tool; return false},
+            AvatarMsg::Req(DrawpadReq::Clear|DrawpadReq::SubmitChain) => return false,
+            AvatarMsg::Save => {
+                let local = unsafe {&*ctx.props().local};
+                match Avatar::encode(self.figure.get_ref()) {
+                    Some(avatar) => {
+                        LocalStorage::set(STORAGE_KEY, &avatar).unwrap_or_default();
+                        self.req_bus.send(Req::SetAvatar(avatar));
+                        self.console.send(ItemKind::Notice { msg: local.avatar_saved.to_string() });
+                    },
+                    None => self.console.send(ItemKind::Warn { msg: local.avatar_too_many_colors.to_string() }),
+                }
+                return false
+            },
+        };
+        self.figure.render_frame(&Frame { instructions });
+        self.sync_figure();
+        false
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let onpointerdown = ctx.link().callback(|evt: PointerEvent| AvatarMsg::Pointer {
+            coor: (evt.offset_x(), evt.offset_y()),
+            down: true,
+            buttons: evt.buttons(),
+        });
+        let onpointermove = ctx.link().callback(|evt: PointerEvent| AvatarMsg::Pointer {
+            coor: (evt.offset_x(), evt.offset_y()),
+            down: false,
+            buttons: evt.buttons(),
+        });
+        let onpointerup = ctx.link().callback(|_| AvatarMsg::Up);
+        let onpointerleave = ctx.link().callback(|_| AvatarMsg::Up);
+        let oncontextmenu = ctx.link().batch_callback(|evt: web_sys::MouseEvent| {
+            evt.prevent_default();
+            None
+        });
+        let local = unsafe {&*ctx.props().local};
+        let save = ctx.link().callback(|_| AvatarMsg::Save);
+        html! {
+            <div class="avatar-editor">
+                <canvas ref={self.canvas_ref.clone()} width={AVATAR_SIZE.to_string()} height={AVATAR_SIZE.to_string()}
+                    {onpointerdown} {onpointermove} {onpointerup} {onpointerleave} {oncontextmenu}/>
+                <button onclick={save}>{local.avatar_save}</button>
+            </div>
+        }
+    }
+
+    fn rendered(&mut self, _ctx: &Context<Self>, first_render: bool) {
+        if first_render {
+            self.sync_figure();
+        }
+    }
+}
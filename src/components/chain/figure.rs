@@ -0,0 +1,107 @@
+use std::rc::Rc;
+
+use gloo_timers::callback::Interval;
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlCanvasElement, CanvasRenderingContext2d};
+use yew::{Component, Context, Html, NodeRef, Properties, html};
+
+use dng_protocol::{FigureLocal, Frame, replay};
+use crate::components::drawpad::paint;
+use crate::consts::{DRAWPAD_W, DRAWPAD_H};
+
+/// milliseconds between two replayed frames, as fast as the drawpad draws them
+const REPLAY_INTERVAL: u32 = 17;
+
+#[derive(Debug, Properties, PartialEq)]
+pub struct FigureCanvasProps {
+    pub frames: Rc<Vec<Frame>>,
+    /// replay the frames one by one, rather than showing the finished drawing
+    #[prop_or_default]
+    pub animate: bool,
+}
+
+pub enum FigureCanvasMsg {
+    Tick,
+}
+
+/// a drawing sent as frames, on a canvas of its own
+pub struct FigureCanvas {
+    canvas_ref: NodeRef,
+    figure: FigureLocal,
+    /// index of the next frame to replay
+    next: usize,
+    ticker: Option<Interval>,
+}
+
+impl FigureCanvas {
+    fn paint(&self) {
+        let ctx = self.canvas_ref.cast::<HtmlCanvasElement>()
+            .and_then(|canvas|canvas.get_context("2d").ok().flatten())
+            .and_then(|ctx|ctx.dyn_into::<CanvasRenderingContext2d>().ok());
+        if let Some(ctx) = ctx {
+            paint(&ctx, &self.figure);
+        }
+    }
+
+    fn start(&mut self, ctx: &Context<Self>) {
+        let props = ctx.props();
+        if props.animate {
+            self.figure = FigureLocal::blank();
+            self.next = 0;
+            let link = ctx.link().clone();
+            self.ticker = Some(Interval::new(REPLAY_INTERVAL, move || link.send_message(FigureCanvasMsg::Tick)));
+        } else {
+            self.figure = replay(&props.frames);
+            self.ticker = None;
+        }
+        self.paint();
+    }
+}
+
+impl Component for FigureCanvas {
+    type Message = FigureCanvasMsg;
+    type Properties = FigureCanvasProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            canvas_ref: NodeRef::default(),
+            figure: FigureLocal::blank(),
+            next: 0,
+            ticker: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            FigureCanvasMsg::Tick => {
+                match ctx.props().frames.get(self.next) {
+                    Some(frame) => {
+                        self.figure.render_frame(frame);
+                        self.next += 1;
+                        self.paint();
+                    },
+                    None => self.ticker = None,
+                }
+                false
+            },
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        // the canvas stays, only what is on it changes
+        self.start(ctx);
+        false
+    }
+
+    fn view(&self, _ctx: &Context<Self>) -> Html {
+        html!(
+            <canvas class = "figure-canvas" ref = {self.canvas_ref.clone()} width = {DRAWPAD_W.to_string()} height = {DRAWPAD_H.to_string()}/>
+        )
+    }
+
+    fn rendered(&mut self, ctx: &Context<Self>, first_render: bool) {
+        if first_render {
+            self.start(ctx);
+        }
+    }
+}
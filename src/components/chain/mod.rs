@@ -0,0 +1,152 @@
+mod figure;
+mod reveal;
+
+use std::rc::Rc;
+
+use web_sys::HtmlInputElement;
+use yew::{Component, Context, Html, NodeRef, html, KeyboardEvent};
+use yew_agent::{Bridge, Bridged, Dispatcher, Dispatched};
+
+use dng_protocol::{ChainTask, Frame};
+use crate::components::drawpad::{DrawpadAgent, DrawpadReq};
+use crate::locals::Lang;
+use crate::state::{ChainStep, GameStateAgent, StateUpdate};
+use crate::ws::{Req, Resp, WsReqAgent};
+pub use figure::FigureCanvas;
+pub use reveal::ChainReveal;
+
+pub enum ChainMsg {
+    State(StateUpdate),
+    Submit,
+}
+
+/// telephone mode, what to do in the step everyone is at
+pub struct ChainPanel {
+    step: Option<ChainStep>,
+    /// the drawing to describe, kept apart so the canvas is not replayed on every update
+    drawing: Option<Rc<Vec<Frame>>>,
+    submitted: bool,
+    lang: Lang,
+    input_ref: NodeRef,
+    req_bus: Dispatcher<WsReqAgent>,
+    drawpad: Dispatcher<DrawpadAgent>,
+    _state_bus: Box<dyn Bridge<GameStateAgent>>,
+}
+
+impl ChainPanel {
+    /// sends the text typed so far, if there is any
+    fn send_text(&mut self) -> bool {
+        let text = self.input_ref.cast::<HtmlInputElement>()
+            .map(|input|input.value().trim().to_string())
+            .unwrap_or_default();
+        if text.is_empty() {
+            return false
+        }
+        self.req_bus.send(Req::ChainText { text });
+        true
+    }
+}
+
+impl Component for ChainPanel {
+    type Message = ChainMsg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        Self {
+            step: None,
+            drawing: None,
+            submitted: false,
+            lang: Lang::default(),
+            input_ref: NodeRef::default(),
+            req_bus: WsReqAgent::dispatcher(),
+            drawpad: DrawpadAgent::dispatcher(),
+            _state_bus: GameStateAgent::bridge(ctx.link().callback(ChainMsg::State)),
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            ChainMsg::State(StateUpdate{state, cause}) => {
+                if let Some(Resp::ChainStepEnd) = cause.as_deref() {
+                    // the drawpad sends the drawing on its own
+                    let is_text = matches!(self.step.as_ref().map(|s|&s.task), Some(ChainTask::Prompt|ChainTask::Describe{..}));
+                    if is_text && !self.submitted {
+                        self.send_text();
+                    }
+                }
+                if state.chain_step == self.step {
+                    let relabeled = state.lang != self.lang && self.step.is_some();
+                    self.lang = state.lang;
+                    return relabeled
+                }
+                self.lang = state.lang;
+                self.step = state.chain_step.clone();
+                self.drawing = match self.step.as_ref().map(|s|&s.task) {
+                    Some(ChainTask::Describe { drawing }) => Some(Rc::new(drawing.clone())),
+                    _ => None,
+                };
+                self.submitted = false;
+                if let Some(input) = self.input_ref.cast::<HtmlInputElement>() {
+                    input.set_value("");
+                }
+                true
+            },
+            ChainMsg::Submit => {
+                match self.step.as_ref().map(|s|&s.task) {
+                    Some(ChainTask::Draw{..}) => {
+                        self.drawpad.send(DrawpadReq::SubmitChain);
+                        self.submitted = true;
+                    },
+                    Some(_) => self.submitted = self.send_text(),
+                    None => {},
+                }
+                true
+            },
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let step = match &self.step {
+            Some(step) => step,
+            None => return html!(),
+        };
+        let local = self.lang.local();
+        let progress = local.chain_progress.replace("{}", &format!("{}/{}", step.step + 1, step.steps));
+        let submit = ctx.link().callback(|_| ChainMsg::Submit);
+        if let ChainTask::Draw { prompt } = &step.task {
+            // a banner only, the drawpad stays free to draw on
+            return html!(
+                <div class = "chain chain-draw">
+                    <span class = "chain-progress"> {progress} </span>
+                    <span class = "chain-prompt"> {local.chain_draw.replace("{}", prompt)} </span>
+                    if self.submitted {
+                        <span class = "chain-waiting"> {local.chain_submitted} </span>
+                    } else {
+                        <button onclick = {submit}> {local.chain_done} </button>
+                    }
+                </div>
+            )
+        }
+        let onkeyup = ctx.link().batch_callback(|evt: KeyboardEvent| {
+            (evt.key() == "Enter").then_some(ChainMsg::Submit)
+        });
+        let (title, drawing) = match &self.drawing {
+            Some(frames) => (local.chain_describe, html!(<FigureCanvas frames = {frames.clone()}/>)),
+            None => (local.chain_prompt, html!()),
+        };
+        html!(
+            <div class = "chain">
+                <div class = "chain-progress"> {progress} </div>
+                <div class = "chain-title"> {title} </div>
+                {drawing}
+                <div class = "chain-input">
+                    <input type = "text" ref = {self.input_ref.clone()} {onkeyup}/>
+                    <button onclick = {submit}> {local.chain_submit} </button>
+                </div>
+                if self.submitted {
+                    <div class = "chain-waiting"> {local.chain_waiting} </div>
+                }
+            </div>
+        )
+    }
+}
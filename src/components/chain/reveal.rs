@@ -0,0 +1,145 @@
+use std::rc::Rc;
+
+use yew::{Component, Context, Html, html};
+use yew_agent::{Bridge, Bridged, Dispatcher, Dispatched};
+
+use dng_protocol::{ChainEntry, Frame};
+use crate::state::{GameState, GameStateAgent, StateUpdate};
+use crate::ws::{Req, Resp, WsReqAgent};
+use super::FigureCanvas;
+
+pub enum RevealMsg {
+    State(StateUpdate),
+    /// to the next link, or the first of the next chain
+    Next,
+    Prev,
+    Ready,
+}
+
+/// telephone mode, walks through every chain link by link once the game is over
+pub struct ChainReveal {
+    state: Rc<GameState>,
+    /// the drawings of every chain, by chain and link, shared with the canvases
+    drawings: Vec<Vec<Option<Rc<Vec<Frame>>>>>,
+    /// chain and link shown
+    at: (usize, usize),
+    dismissed: bool,
+    req_bus: Dispatcher<WsReqAgent>,
+    _state_bus: Box<dyn Bridge<GameStateAgent>>,
+}
+
+impl ChainReveal {
+    fn name_of(&self, idx: u8) -> String {
+        self.state.players.iter()
+            .find(|p|p.idx == idx)
+            .map(|p|p.name.clone())
+            .unwrap_or_else(|| self.state.lang.local().seat.replace("{}", &idx.to_string()))
+    }
+
+    fn links_of(&self, chain: usize) -> usize {
+        self.state.chains.get(chain).map(|c|c.links.len()).unwrap_or(0)
+    }
+}
+
+impl Component for ChainReveal {
+    type Message = RevealMsg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        Self {
+            state: Rc::new(GameState::default()),
+            drawings: Vec::new(),
+            at: (0, 0),
+            dismissed: false,
+            req_bus: WsReqAgent::dispatcher(),
+            _state_bus: GameStateAgent::bridge(ctx.link().callback(RevealMsg::State)),
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            RevealMsg::State(StateUpdate{state, cause}) => {
+                if let Some(Resp::ChainReveal { chains }) = cause.as_deref() {
+                    self.drawings = chains.iter().map(|chain|chain.links.iter().map(|link|match &link.entry {
+                        ChainEntry::Drawing(frames) => Some(Rc::new(frames.clone())),
+                        ChainEntry::Text(_) => None,
+                    }).collect()).collect();
+                    self.at = (0, 0);
+                    self.dismissed = false;
+                }
+                let changed = state.chains != self.state.chains
+                    || state.players != self.state.players
+                    || state.lang != self.state.lang;
+                self.state = state;
+                changed
+            },
+            RevealMsg::Next => {
+                let (chain, link) = self.at;
+                if link + 1 < self.links_of(chain) {
+                    self.at = (chain, link + 1);
+                } else if chain + 1 < self.state.chains.len() {
+                    self.at = (chain + 1, 0);
+                }
+                true
+            },
+            RevealMsg::Prev => {
+                let (chain, link) = self.at;
+                if link > 0 {
+                    self.at = (chain, link - 1);
+                } else if chain > 0 {
+                    self.at = (chain - 1, self.links_of(chain - 1).saturating_sub(1));
+                }
+                true
+            },
+            RevealMsg::Ready => {
+                self.req_bus.send(Req::ImReady);
+                self.dismissed = true;
+                true
+            },
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let local = self.state.lang.local();
+        let (chain_idx, link_idx) = self.at;
+        let chain = match self.state.chains.get(chain_idx) {
+            Some(chain) if !self.dismissed => chain,
+            _ => return html!(),
+        };
+        let entry = match chain.links.get(link_idx) {
+            Some(link) => {
+                let content = match (&link.entry, self.drawings.get(chain_idx).and_then(|d|d.get(link_idx)).cloned().flatten()) {
+                    (_, Some(frames)) => html!(<FigureCanvas {frames} animate = {true}/>),
+                    (ChainEntry::Text(text), None) => html!(<div class = "chain-text"> {text.clone()} </div>),
+                    (ChainEntry::Drawing(_), None) => html!(),
+                };
+                html!(
+                    <div class = "chain-link">
+                        <div class = "chain-author"> {self.name_of(link.author)} </div>
+                        {content}
+                    </div>
+                )
+            },
+            None => html!(),
+        };
+        let is_first = self.at == (0, 0);
+        let is_last = chain_idx + 1 >= self.state.chains.len() && link_idx + 1 >= chain.links.len();
+        let prev = ctx.link().callback(|_| RevealMsg::Prev);
+        let next = ctx.link().callback(|_| RevealMsg::Next);
+        let ready = ctx.link().callback(|_| RevealMsg::Ready);
+        html!(
+            <div class = "chain-reveal">
+                <div class = "chain-reveal-title">
+                    {format!("{} ({}/{})", local.chain_owner.replace("{}", &self.name_of(chain.owner)), chain_idx + 1, self.state.chains.len())}
+                </div>
+                {entry}
+                <div class = "chain-reveal-nav">
+                    <button onclick = {prev} disabled = {is_first}> {local.chain_prev} </button>
+                    <span> {format!("{}/{}", link_idx + 1, chain.links.len())} </span>
+                    <button onclick = {next} disabled = {is_last}> {local.chain_next} </button>
+                </div>
+                <button class = "results-ready" onclick = {ready}> {local.results_ready} </button>
+            </div>
+        )
+    }
+}
@@ -0,0 +1,99 @@
+use gloo_timers::callback::Interval;
+use yew::{Component, Context, Html, html};
+use yew_agent::{Bridge, Bridged, Dispatcher, Dispatched};
+
+use crate::locals::Lang;
+use crate::state::{GameStateAgent, StateUpdate};
+use crate::ws::{Req, Resp, WsReqAgent};
+
+pub enum ChoiceMsg {
+    State(StateUpdate),
+    Choose(u8),
+    Tick,
+}
+
+/// buttons for the drawer to pick the topic, the first one is taken when time runs out
+pub struct TopicChoice {
+    candidates: Vec<String>,
+    seconds_left: u8,
+    lang: Lang,
+    ticker: Option<Interval>,
+    req_bus: Dispatcher<WsReqAgent>,
+    _state_bus: Box<dyn Bridge<GameStateAgent>>,
+}
+
+impl TopicChoice {
+    fn close(&mut self) {
+        self.candidates.clear();
+        self.ticker = None;
+    }
+}
+
+impl Component for TopicChoice {
+    type Message = ChoiceMsg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        Self {
+            candidates: Vec::new(),
+            seconds_left: 0,
+            lang: Lang::default(),
+            ticker: None,
+            req_bus: WsReqAgent::dispatcher(),
+            _state_bus: GameStateAgent::bridge(ctx.link().callback(ChoiceMsg::State)),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            ChoiceMsg::State(StateUpdate{state, cause}) => {
+                let relabeled = state.lang != self.lang && !self.candidates.is_empty();
+                self.lang = state.lang;
+                match cause.as_deref() {
+                    Some(Resp::TopicChoices { candidates, timeout }) if !candidates.is_empty() => {
+                        self.candidates = candidates.clone();
+                        self.seconds_left = *timeout;
+                        let link = ctx.link().clone();
+                        self.ticker = Some(Interval::new(1000, move || link.send_message(ChoiceMsg::Tick)));
+                        true
+                    },
+                    _ if state.topic_choices.is_none() && !self.candidates.is_empty() => {
+                        self.close();
+                        true
+                    },
+                    _ => relabeled,
+                }
+            },
+            ChoiceMsg::Choose(idx) => {
+                self.req_bus.send(Req::ChooseTopic { idx });
+                self.close();
+                true
+            },
+            ChoiceMsg::Tick => {
+                self.seconds_left = self.seconds_left.saturating_sub(1);
+                if self.seconds_left == 0 {
+                    ctx.link().send_message(ChoiceMsg::Choose(0));
+                }
+                true
+            },
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        if self.candidates.is_empty() {
+            return html!();
+        }
+        let local = self.lang.local();
+        let buttons: Vec<Html> = self.candidates.iter().zip(0u8..).map(|(word, idx)| {
+            let onclick = ctx.link().callback(move |_| ChoiceMsg::Choose(idx));
+            html!(<button class = "choice-candidate" {onclick}> {word.clone()} </button>)
+        }).collect();
+        html!(
+            <div class = "choice">
+                <div class = "choice-title"> {local.choice_title} </div>
+                <div class = "choice-candidates"> {buttons} </div>
+                <div class = "choice-timeout"> {local.choice_timeout.replace("{}", &self.seconds_left.to_string())} </div>
+            </div>
+        )
+    }
+}
@@ -4,7 +4,7 @@ use web_sys::HtmlInputElement;
 use yew::{prelude::*, virtual_dom::VNode};
 use yew_agent::{Dispatched};
 
-use crate::{ws::{WsReqAgent}};
+use crate::{ws::{WsReqAgent, Diagnostics}};
 
 
 #[derive(Debug, Clone, PartialEq)]
@@ -17,6 +17,11 @@ pub enum ItemKind {
         sender: String,
         msg: String,
     },
+    TeamChat {
+        local: *const crate::locals::Locals<'static>,
+        sender: String,
+        msg: String,
+    },
     Notice {
         msg: String
     },
@@ -27,11 +32,32 @@ pub enum ItemKind {
         msg: String
     },
     Poll {
-        local: *const crate::locals::Locals<'static>
+        local: *const crate::locals::Locals<'static>,
+        /// tells the radio inputs of different polls apart
+        id: usize,
+        closed: bool,
+    },
+    MarkResult {
+        local: *const crate::locals::Locals<'static>,
+        stars: [u8; 5],
+    },
+    VoteKick {
+        local: *const crate::locals::Locals<'static>,
+        id: usize,
+        target: u8,
+        msg: String,
+        closed: bool,
     },
     Help {
         local: *const crate::locals::Locals<'static>
-    }
+    },
+    Diagnostics {
+        local: *const crate::locals::Locals<'static>,
+        diag: Diagnostics,
+    },
+    AvatarEditor {
+        local: *const crate::locals::Locals<'static>
+    },
 }
 
 #[derive(Debug, Clone, Properties, PartialEq)]
@@ -59,6 +85,15 @@ pub fn item(props: &ItemProps) -> Html {
                 </div>
             }
         },
+        TeamChat{local, sender, msg} => {
+            let local = unsafe {&*(*local)};
+            let msg = format!("{} {}: {}", local.team_chat_tag, sender, msg);
+            html! {
+                <div class="chat team-chat">
+                    {msg}
+                </div>
+            }
+        },
         Notice{msg} => {
             html! {
                 <div class="notice">
@@ -81,36 +116,122 @@ pub fn item(props: &ItemProps) -> Html {
             }
         },
         
-        Poll{local} => {
+        Poll{local, id, closed} => {
             let local = unsafe {&*(*local)};
             let oninput = Callback::from(move |evt:InputEvent|{
                 let mut ws = WsReqAgent::dispatcher();
                 let target = evt.target().unwrap().dyn_into::<HtmlInputElement>().unwrap();
-                let score = i8::from_str_radix(target.value().as_str(), 10).unwrap();
-                ws.send(crate::ws::Req::Mark{score});
+                if let Ok(stars) = target.value().parse::<u8>() {
+                    ws.send(crate::ws::Req::Rate{stars});
+                }
             });
+            // reversed, so that css can light up every star left of the checked one
+            let stars:Vec<VNode> = (1..=5u8).rev().map(|n|{
+                let input_id = format!("poll-{}-star-{}", id, n);
+                let title = match n {
+                    1 => local.vote_down,
+                    3 => local.vote_neutral,
+                    5 => local.vote_up,
+                    _ => "",
+                };
+                html! {
+                    <>
+                        <input type="radio" name={format!("vote-{}", id)} id={input_id.clone()} value={n.to_string()} disabled={*closed}/>
+                        <label for={input_id} class="poll-star" {title}>{"★"}</label>
+                    </>
+                }
+            }).collect();
             html! {
-                <div class="poll">
+                <div class={classes!("poll", closed.then_some("poll-closed"))}>
                     <form {oninput}>
                         <div class="poll-topic">{local.mark}</div>
-                        <div class="poll-option" >
-                            <label id="option-1">
-                                <input type="radio" name="vote" value="-1"/>
-                                <div class="poll-option-label" style = "color:#ff7777;">{local.vote_down}</div>
-                            </label>
-                            <label id="option-2">
-                                <input type="radio"  name="vote" value="0"/>
-                                <div class="poll-option-label" style = "color:#ccffcc;">{local.vote_neutral}</div>
+                        <div class="poll-stars">{stars}</div>
+                    </form>
+                </div>
+            }
+        }
+        VoteKick{local, id, target, msg, closed} => {
+            let local = unsafe {&*(*local)};
+            let target = *target;
+            let oninput = Callback::from(move |evt:InputEvent|{
+                let mut ws = WsReqAgent::dispatcher();
+                let target_input = evt.target().unwrap().dyn_into::<HtmlInputElement>().unwrap();
+                ws.send(crate::ws::Req::KickBallot{target, agree: target_input.value() == "agree"});
+            });
+            let name = format!("votekick-{}", id);
+            html! {
+                <div class={classes!("poll", closed.then_some("poll-closed"))}>
+                    <form {oninput}>
+                        <div class="poll-topic">{msg}</div>
+                        <div class="poll-option">
+                            <label>
+                                <input type="radio" name={name.clone()} value="agree" disabled={*closed}/>
+                                <div class="poll-option-label" style = "color:#ff7777;">{local.agree}</div>
                             </label>
-                            <label id="option-3">
-                                <input type="radio"  name="vote" value="1"/>
-                                <div class="poll-option-label" style = "color:#55ccff;">{local.vote_up}</div>
+                            <label>
+                                <input type="radio" name={name} value="disagree" disabled={*closed}/>
+                                <div class="poll-option-label" style = "color:#55ccff;">{local.disagree}</div>
                             </label>
                         </div>
                     </form>
                 </div>
             }
         }
+        MarkResult{local, stars} => {
+            let local = unsafe {&*(*local)};
+            let total:u32 = stars.iter().map(|n|*n as u32).sum();
+            let weighted:u32 = stars.iter().zip(1..).map(|(n, s)|*n as u32 * s).sum();
+            let max = stars.iter().copied().max().unwrap_or(0).max(1) as u32;
+            let rows:Vec<VNode> = stars.iter().zip(1u8..=5).rev().map(|(n, s)|{
+                let width = format!("width: {}%;", *n as u32 * 100 / max);
+                html! {
+                    <div class="mark-result-row">
+                        <span>{format!("{}★", s)}</span>
+                        <span class="mark-result-bar"><span style={width}></span></span>
+                        <span>{n}</span>
+                    </div>
+                }
+            }).collect();
+            let average = if total == 0 {
+                format!("{} --", local.average)
+            } else {
+                format!("{} {:.1}★ / {}", local.average, weighted as f32 / total as f32, total)
+            };
+            html! {
+                <div class="mark-result">
+                    {rows}
+                    <div>{average}</div>
+                </div>
+            }
+        }
+        Diagnostics{local, diag} => {
+            let local = unsafe {&*(*local)};
+            let mut lines = vec![
+                format!("{}: {}", local.diag_sent, diag.sent),
+                format!("{}: {}", local.diag_received, diag.received),
+                format!("{}: {}", local.diag_encode_errors, diag.encode_errors),
+                format!("{}: {}", local.diag_decode_errors, diag.decode_errors),
+                format!("{}: {}", local.diag_socket_errors, diag.socket_errors),
+            ];
+            if let Some((size, reason)) = &diag.last_decode_error {
+                lines.push(format!("{}: {}, {}", local.diag_last_decode_error, local.diag_bytes.replace("{}", &size.to_string()), reason));
+            }
+            if let Some(e) = &diag.last_socket_error {
+                lines.push(format!("{}: {}", local.diag_last_socket_error, e));
+            }
+            let lines:Vec<VNode> = lines.into_iter().map(|line|html!(<span>{line}<br/></span>)).collect();
+            html! {
+                <div class="diagnostics">
+                    {"diag> "}<br/>
+                    {lines}
+                </div>
+            }
+        }
+        AvatarEditor{local} => {
+            html! {
+                <crate::components::avatar::AvatarEditor local={*local}/>
+            }
+        }
         Help{local} => {
             let local = unsafe {&*(*local)};
 
@@ -8,35 +8,103 @@ use yew::prelude::*;
 use agent::ConsoleAgent;
 use item::{ItemProps, Item};
 use web_sys::{HtmlInputElement, HtmlElement};
-use crate::{rgb, locals};
-use crate::ws::{WsRespAgent, WsReqAgent, Resp, Req};
+use crate::locals;
+use dng_protocol::rgb;
+use crate::ws::{WsRespAgent, WsReqAgent, WsStatusAgent, WsStatus, Diagnostics, Resp, Req, RecorderAgent, RecorderInput};
 
-use crate::components::drawpad::{DrawpadAgent, DrawpadReq, StreamMode};
+use crate::components::drawpad::{DrawpadAgent, DrawpadReq};
+use crate::state::{GameState, GameStateAgent, StateInput, StateUpdate, StreamMode};
+use crate::components::room::{describe_config, parse_config};
 
 pub struct Console {
 
     local: locals::Locals<'static>, 
 
     items: Vec<ItemProps>,
+    diagnostics: Diagnostics,
+    state: Rc<GameState>,
+    /// plain input goes to the own team only
+    team_chat: bool,
     input_ref: NodeRef,
     output_ref: NodeRef,
 
     req_bus: Dispatcher<WsReqAgent>,
+    recorder: Dispatcher<RecorderAgent>,
     
     drawpad_agent: Dispatcher<DrawpadAgent>,
 
 
     resp_bus: Option<Box<dyn Bridge<WsRespAgent>>>,
+    status_bus: Option<Box<dyn Bridge<WsStatusAgent>>>,
+    state_bus: Option<Box<dyn Bridge<GameStateAgent>>>,
     agent: Option<Box<dyn Bridge<ConsoleAgent>>>,
 }
 
 
 pub enum ConsoleMsg {
     Ws(Rc<Resp>),
+    Status(WsStatus),
+    State(StateUpdate),
     AddItem(ItemProps),
+    ToggleTeamChat,
     Submit
 }
 
+/// the console item a response shows up as, if any, `id` tells polls apart
+pub fn item_of(resp: &Resp, local: &locals::Locals<'static>, state: &GameState, id: usize) -> Option<item::ItemKind> {
+    use item::ItemKind::*;
+    let name_of = |idx: u8| state.players.iter()
+        .find(|p|p.idx == idx)
+        .map(|p|p.name.clone())
+        .unwrap_or_else(|| local.seat.replace("{}", &idx.to_string()));
+    let kind = match resp {
+        Resp::Chat { sender, msg } => Chat { sender: sender.clone(), msg: msg.clone()},
+        Resp::TeamChat { sender, msg } => TeamChat { local, sender: sender.clone(), msg: msg.clone()},
+        Resp::Notice { msg } => Notice { msg: msg.clone()},
+        Resp::Warn { msg } => Warn { msg: msg.clone()},
+        Resp::GameStart => GameState { msg: local.game_start.into() },
+        Resp::GameEnd => GameState { msg: local.game_end.into() },
+        Resp::Topic { topic_word } => GameState { msg: format!("{}{}", local.key_word, topic_word) },
+        Resp::TurnStart(_) => GameState { msg: local.turn_start.into() },
+        Resp::TurnEnd => GameState { msg: local.turn_end.into() },
+        Resp::MarkStart => GameState { msg: local.mark_start.into() },
+        Resp::Poll => Poll {local, id, closed: false},
+        Resp::MarkEnd => GameState { msg: local.mark_end.into() },
+        Resp::MarkResult { stars, .. } => MarkResult { local, stars: *stars },
+        Resp::VoteKick { target, initiator } => VoteKick {
+            local,
+            id,
+            target: *target,
+            msg: format!("{} {} ({})", local.vote_kick, name_of(*target), name_of(*initiator)),
+            closed: false,
+        },
+        Resp::VoteKickEnd { target, kicked } => GameState {
+            msg: format!("{} {}", name_of(*target), if *kicked {local.kicked} else {local.not_kicked}),
+        },
+        _ => return None,
+    };
+    Some(kind)
+}
+
+impl Console {
+    fn send_state(&mut self, input: StateInput) {
+        if let Some(state_bus) = self.state_bus.as_mut() {
+            state_bus.send(input);
+        }
+    }
+
+    fn toggle_team_chat(&mut self) {
+        let local = &self.local;
+        if self.team_chat || self.state.me().and_then(|me|me.team).is_some() {
+            self.team_chat = !self.team_chat;
+            let msg = if self.team_chat {local.team_chat_on} else {local.team_chat_off};
+            self.items.push(ItemProps{kind:item::ItemKind::Command { task: "team".into(), msg: msg.into() }});
+        } else {
+            self.items.push(ItemProps{kind:item::ItemKind::Warn{ msg: local.no_team.into() }});
+        }
+    }
+}
+
 impl Component for Console {
     type Message = ConsoleMsg;
     type Properties = ();
@@ -46,12 +114,18 @@ impl Component for Console {
         Self {
             items: vec![ItemProps{kind:item::ItemKind::Help{local: &locals::ZH}}],
 
+            diagnostics: Diagnostics::default(),
+            state: Rc::new(GameState::default()),
+            team_chat: false,
             local: locals::ZH,
             input_ref: NodeRef::default(),
             output_ref: NodeRef::default(),
             req_bus: WsReqAgent::dispatcher(),
+            recorder: RecorderAgent::dispatcher(),
             drawpad_agent: DrawpadAgent::dispatcher(),
             resp_bus:None,
+            status_bus: None,
+            state_bus: None,
             agent: None
         }
     }
@@ -61,10 +135,26 @@ impl Component for Console {
         use ConsoleMsg::*;
         use item::ItemKind::*;
         match msg {
+            Status(WsStatus::Diagnostics(diagnostics)) => {
+                self.diagnostics = diagnostics;
+                false
+            },
+            Status(_) => false,
+            State(StateUpdate{state, ..}) => {
+                // leaving the team mode takes the channel along
+                let was_team_chat = self.team_chat;
+                self.team_chat &= state.me().and_then(|me|me.team).is_some();
+                self.state = state;
+                was_team_chat != self.team_chat
+            },
             AddItem(props) => {
                 self.items.push(props);
                 true
             },
+            ToggleTeamChat => {
+                self.toggle_team_chat();
+                true
+            },
             Submit => {
                 if let Some(input_element) = self.input_ref.cast::<HtmlInputElement>() {
                     let input = input_element.value();
@@ -85,8 +175,14 @@ impl Component for Console {
                                 if let Some(lang) = paras.next() {
                                     use crate::locals::*;
                                     match lang.to_ascii_lowercase().as_str() {
-                                        "zh" => self.local = ZH,
-                                        "en" => self.local = EN,
+                                        "zh" => {
+                                            self.local = ZH;
+                                            self.send_state(StateInput::SetLang(Lang::Zh));
+                                        },
+                                        "en" => {
+                                            self.local = EN;
+                                            self.send_state(StateInput::SetLang(Lang::En));
+                                        },
                                         _ => {
                                             self.items.push(ItemProps{kind:Command { task: "lang".into(), msg: "no such localization, but you may help to translate".into() }});
 
@@ -109,9 +205,9 @@ impl Component for Console {
                             },
                             Some("/mode") => {
                                 match paras.next() {
-                                    Some("watch") => {self.drawpad_agent.send(DrawpadReq::SetStreamMode(StreamMode::Receive))}
-                                    Some("draw") => {self.drawpad_agent.send(DrawpadReq::SetStreamMode(StreamMode::Push))}
-                                    Some("offline") => {self.drawpad_agent.send(DrawpadReq::SetStreamMode(StreamMode::Offline))}
+                                    Some("watch") => {self.send_state(StateInput::SetStreamMode(StreamMode::Receive))}
+                                    Some("draw") => {self.send_state(StateInput::SetStreamMode(StreamMode::Push))}
+                                    Some("offline") => {self.send_state(StateInput::SetStreamMode(StreamMode::Offline))}
                                     _ => {}
                                 }
                             },
@@ -133,11 +229,86 @@ impl Component for Console {
                                     self.items.push(ItemProps{kind:Warn{ msg: local.unsupported.into() }})
                                 }
                             },
+                            Some("/record") => match paras.next() {
+                                Some("start") => {
+                                    self.recorder.send(RecorderInput::Start);
+                                    self.items.push(ItemProps{kind:Command { task: "record".into(), msg: local.record_started.into() }});
+                                },
+                                Some("stop") => {
+                                    self.recorder.send(RecorderInput::Stop);
+                                    self.items.push(ItemProps{kind:Command { task: "record".into(), msg: local.record_stopped.into() }});
+                                },
+                                Some("save") => self.recorder.send(RecorderInput::Save),
+                                Some("clear") => self.recorder.send(RecorderInput::Clear),
+                                _ => {
+                                    self.items.push(ItemProps{kind:Warn{ msg: local.unsupported.into() }})
+                                }
+                            },
+                            Some("/config") => match (paras.next(), paras.next()) {
+                                (None, _) => {
+                                    let msg = match &self.state.config {
+                                        Some(config) => describe_config(config, local),
+                                        None => "--".to_string(),
+                                    };
+                                    self.items.push(ItemProps{kind:Command { task: "config".into(), msg }})
+                                },
+                                (Some(key), Some(value)) => match parse_config(key, value) {
+                                    Some(change) if self.state.is_host() => self.req_bus.send(Req::Config(change)),
+                                    Some(_) => self.items.push(ItemProps{kind:Warn{ msg: local.host_only.into() }}),
+                                    None => self.items.push(ItemProps{kind:Warn{ msg: local.check_your_input.into() }}),
+                                },
+                                (Some(_), None) => {
+                                    self.items.push(ItemProps{kind:Warn{ msg: local.lack_of_parameter.into() }})
+                                }
+                            },
+                            Some(cmd @ ("/kick"|"/votekick")) => match paras.next().map(|name|self.state.player_by_name(name)) {
+                                Some(Some(player)) => {
+                                    let target = player.idx;
+                                    if cmd == "/votekick" {
+                                        self.req_bus.send(Req::VoteKick { target })
+                                    } else if self.state.is_host() {
+                                        self.req_bus.send(Req::Kick { target })
+                                    } else {
+                                        self.items.push(ItemProps{kind:Warn{ msg: local.host_only.into() }})
+                                    }
+                                },
+                                Some(None) => self.items.push(ItemProps{kind:Warn{ msg: local.no_such_player.into() }}),
+                                None => self.items.push(ItemProps{kind:Warn{ msg: local.lack_of_parameter.into() }}),
+                            },
+                            Some("/mute") => match paras.next() {
+                                Some(name) => {
+                                    let hide_drawing = paras.next() == Some("canvas");
+                                    self.items.push(ItemProps{kind:Command { task: "mute".into(), msg: format!("{} {}", local.muted, name) }});
+                                    self.send_state(StateInput::Mute { name: name.into(), hide_drawing });
+                                },
+                                None => self.items.push(ItemProps{kind:Warn{ msg: local.lack_of_parameter.into() }}),
+                            },
+                            Some("/unmute") => match paras.next() {
+                                Some(name) => {
+                                    self.items.push(ItemProps{kind:Command { task: "mute".into(), msg: format!("{} {}", local.unmuted, name) }});
+                                    self.send_state(StateInput::Unmute { name: name.into() });
+                                },
+                                None => self.items.push(ItemProps{kind:Warn{ msg: local.lack_of_parameter.into() }}),
+                            },
+                            Some("/avatar") => {
+                                self.items.push(ItemProps{kind:AvatarEditor{local}})
+                            },
+                            Some("/team") => self.toggle_team_chat(),
+                            Some("/diag") => {
+                                self.items.push(ItemProps{kind:Diagnostics{local, diag: self.diagnostics.clone()}})
+                            },
                             Some("/help") => {
                                 self.items.push(ItemProps{kind:Help{local}})
                             }
                             _ => {}
                         }
+                    } else if self.team_chat {
+                        self.items.push(
+                            ItemProps{
+                                kind: TeamChat { local, sender: local.me.into() , msg: input.into() }
+                            }
+                        );
+                        self.req_bus.send(Req::TeamChat { msg: input.into() });
                     } else {
                         self.items.push(
                             ItemProps{
@@ -154,21 +325,26 @@ impl Component for Console {
             },
             Ws(resp) => {
                 let local = &self.local;
-                let kind =  match resp.as_ref() {
-                    Resp::Chat { sender, msg } => Chat { sender: sender.clone(), msg: msg.clone()},
-                    Resp::Notice { msg } => Notice { msg: msg.clone()},
-                    Resp::Warn { msg } => Warn { msg: msg.clone()},
-                    Resp::GameStart => GameState { msg: local.game_start.into() },
-                    Resp::GameEnd => GameState { msg: local.game_end.into() },
-                    Resp::Topic { topic_word } => GameState { msg: format!("{}{}", local.key_word, topic_word) },
-                    Resp::TurnStart(_) => GameState { msg: local.turn_start.into() },
-                    Resp::TurnEnd => GameState { msg: local.turn_end.into() },
-                    Resp::MarkStart => GameState { msg: local.mark_start.into() },
-                    Resp::Poll => Poll {local: local},
-                    Resp::MarkEnd => GameState { msg: local.mark_end.into() },
-                    _ => {
-                        return false;
-                    }
+                match resp.as_ref() {
+                    // votes can be changed until here
+                    Resp::MarkEnd => for item in &mut self.items {
+                        if let Poll { closed, .. } = &mut item.kind {
+                            *closed = true;
+                        }
+                    },
+                    Resp::VoteKickEnd { target: ended, .. } => for item in &mut self.items {
+                        if let VoteKick { target, closed, .. } = &mut item.kind {
+                            if target == ended {
+                                *closed = true;
+                            }
+                        }
+                    },
+                    Resp::Chat { sender, .. } | Resp::TeamChat { sender, .. } if self.state.muted.contains_key(sender) => return false,
+                    _ => {}
+                }
+                let kind = match item_of(resp.as_ref(), local, &self.state, self.items.len()) {
+                    Some(kind) => kind,
+                    None => return false,
                 };
                 self.items.push(ItemProps{kind});
                 true
@@ -191,10 +367,19 @@ impl Component for Console {
             }
         });
 
+        let team_toggle = self.state.me().and_then(|me|me.team).map(|team|{
+            let onclick = ctx.link().callback(|_|Self::Message::ToggleTeamChat);
+            let class = classes!("team-toggle", format!("team-{}", team % 4), self.team_chat.then_some("team-toggle-on"));
+            html!(<button {class} {onclick}>{local.team_toggle}</button>)
+        });
+
         html! {
             <div class="console">
                 <div class="output" ref = {self.output_ref.clone()}>{vnodes}</div>
-                <input type="text" placeholder={local.input_placeholder} ref = {self.input_ref.clone()} {onkeyup}/>
+                <div class="console-input">
+                    {team_toggle.unwrap_or_default()}
+                    <input type="text" placeholder={if self.team_chat {local.team_placeholder} else {local.input_placeholder}} ref = {self.input_ref.clone()} {onkeyup}/>
+                </div>
             </div>
         }
     }
@@ -203,6 +388,8 @@ impl Component for Console {
         if first_render {
             self.agent = Some(ConsoleAgent::bridge(ctx.link().callback(ConsoleMsg::AddItem)));
             self.resp_bus =  Some(WsRespAgent::bridge(ctx.link().callback(ConsoleMsg::Ws)));
+            self.status_bus = Some(WsStatusAgent::bridge(ctx.link().callback(ConsoleMsg::Status)));
+            self.state_bus = Some(GameStateAgent::bridge(ctx.link().callback(ConsoleMsg::State)));
         }
         if let Some(output) = self.output_ref.cast::<HtmlElement>() {
             crate::info!("im here");
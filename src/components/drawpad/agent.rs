@@ -1,18 +1,22 @@
+use std::collections::HashSet;
+
 use yew_agent::{Agent, AgentLink, Context, HandlerId};
 
 
-use super::{figure::{Color, Tool}};
-use super::StreamMode;
+use super::{Color, Tool};
+#[derive(Clone)]
 pub enum DrawpadReq {
     SetColor(Color),
     SetTool(Tool),
     Clear,
-    SetStreamMode(StreamMode)
+    /// telephone mode, the drawing of this step is done
+    SubmitChain,
 }
 
+/// passes the toolbar and console requests on to the drawpad, and to the avatar editor when it is open
 pub struct DrawpadAgent {
     link: AgentLink<Self>,
-    drawpad: Option<HandlerId>,
+    drawpads: HashSet<HandlerId>,
 }
 
 impl Agent for DrawpadAgent {
@@ -24,7 +28,7 @@ impl Agent for DrawpadAgent {
     fn create(link: AgentLink<Self>) -> Self {
         Self {
             link,
-            drawpad: None,
+            drawpads: HashSet::new(),
         }
     }
 
@@ -33,18 +37,18 @@ impl Agent for DrawpadAgent {
     }
 
     fn handle_input(&mut self, msg: Self::Input, _id: HandlerId) {
-
-        if let Some(drawpad) = self.drawpad {
-            self.link.respond(drawpad, msg);
-        } 
-        
+        for drawpad in &self.drawpads {
+            if drawpad.is_respondable() {
+                self.link.respond(*drawpad, msg.clone());
+            }
+        }
     }
 
     fn connected(&mut self, id: HandlerId) {
-        self.drawpad.replace(id);
+        self.drawpads.insert(id);
     }
 
-    fn disconnected(&mut self, _id: HandlerId) {
-        self.drawpad.take();
+    fn disconnected(&mut self, id: HandlerId) {
+        self.drawpads.remove(&id);
     }
-}
\ No newline at end of file
+}
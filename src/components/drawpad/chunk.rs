@@ -1,4 +1,4 @@
-use super::Frame;
+use dng_protocol::Frame;
 
 pub struct ChunkUnloader {
     chunk: Vec<Frame>,
@@ -1,4 +1,4 @@
-use std::{vec, rc::Rc};
+use std::vec;
 
 use bincode::serialize;
 use gloo_timers::callback::Interval;
@@ -9,36 +9,29 @@ use yew::events::{PointerEvent, KeyboardEvent, MouseEvent};
 use yew_agent::{Bridge, Bridged, Dispatcher, Dispatched};
 // use serde::{Serialize, Deserialize};
 
-mod figure;
 mod agent;
 mod chunk;
-mod frame;
 
 
 use chunk::{ChunkLoader, ChunkUnloader};
-pub use figure::Color;
 pub use agent::{DrawpadReq, DrawpadAgent};
 
 
 use crate::info;
-use crate::{rgb, consts::*, ws::{WsReqAgent, WsRespAgent},/*  info */};
-use figure::{FigureLocal, Instruction};
+use crate::{consts::*, ws::WsReqAgent,/*  info */};
+use crate::state::{GameState, GameStateAgent, InkMeter, Reveal, StateInput, StateUpdate, StreamMode};
+use dng_protocol::{rgb, FigureLocal, ChainTask, RevealStyle, REVEAL_TILES};
 use crate::ws::{Req, Resp};
-use frame::Frame;
+pub use dng_protocol::{Color, Tool, Instruction, Frame};
 
-pub use self::figure::Tool;
-
-#[derive(Debug, PartialEq, Eq)]
-pub enum StreamMode {
-    Push,
-    Receive,
-    Offline,
-}
 pub struct Drawpad {
     color: Color,
     tool: Tool,
 
     buttons: u16,
+    ink: InkMeter,
+    /// the stroke being drawn uses ink
+    inking: bool,
 
     canvas_ref: NodeRef,
     figure: FigureLocal,
@@ -48,14 +41,21 @@ pub struct Drawpad {
     chunk_unloader: ChunkUnloader,
     chunk_loader: ChunkLoader<15>,
     stream_mode: StreamMode,
+    /// telephone mode, every frame of the drawing of this step
+    chain_frames: Option<Vec<Frame>>,
+    /// reveal mode, the tiles still covered
+    mask: Option<[bool; REVEAL_TILES * REVEAL_TILES]>,
+    /// reveal mode, frames of the drawing handed to the unloader so far
+    revealed: usize,
 
     req_bus: Dispatcher<WsReqAgent>,
+    state_agent: Dispatcher<GameStateAgent>,
 
 
     frame_handle: Option<Interval>,
 
     console_bus: Option<Box<dyn Bridge<DrawpadAgent>>>,
-    resp_bus: Option<Box<dyn Bridge<WsRespAgent>>>,
+    state_bus: Option<Box<dyn Bridge<GameStateAgent>>>,
 
 }
 
@@ -77,7 +77,34 @@ pub enum DrawpadMsg {
     CtrlX,
     HotKeyE,
     Req(DrawpadReq),
-    Ws(Rc<Resp>)
+    State(StateUpdate)
+}
+
+/// writes `figure` to the canvas of `ctx`, which is as large as the drawpad
+pub fn paint(ctx: &CanvasRenderingContext2d, figure: &FigureLocal) {
+    let raw = figure.get_ref();
+    let mut data = vec![0u8; DRAWPAD_W*DRAWPAD_H*4];
+    // row by row, while the figure keeps its pixels by column
+    for (idx, pixel) in data.chunks_exact_mut(4).enumerate() {
+        let c = raw[idx % DRAWPAD_W][idx / DRAWPAD_W];
+        pixel.copy_from_slice(&[c.r, c.g, c.b, 0xff]);
+    }
+    if let Ok(canvas_data) = ImageData::new_with_u8_clamped_array(Clamped(&data), DRAWPAD_W as u32) {
+        ctx.put_image_data(&canvas_data, 0.0, 0.0).unwrap_or_default();
+    }
+}
+
+/// covers the tiles of the drawpad which reveal mode still hides
+fn cover(ctx: &CanvasRenderingContext2d, mask: &[bool]) {
+    const TILE: usize = DRAWPAD_W / REVEAL_TILES;
+    let gray = [0x84, 0x7e, 0x87, 0xff].repeat(TILE * TILE);
+    if let Ok(tile) = ImageData::new_with_u8_clamped_array(Clamped(&gray), TILE as u32) {
+        for (idx, _) in mask.iter().enumerate().filter(|(_, hidden)|**hidden) {
+            // column-major, like the figure
+            let (x, y) = (idx / REVEAL_TILES, idx % REVEAL_TILES);
+            ctx.put_image_data(&tile, (x * TILE) as f64, (y * TILE) as f64).unwrap_or_default();
+        }
+    }
 }
 
 impl Drawpad {
@@ -101,25 +128,27 @@ impl Drawpad {
 
     fn sync_figure(&mut self)  {
         if let Some(ctx) = self.get_context() {
-            let raw = self.figure.get_ref();
-            let data = unsafe {
-                use std::mem::MaybeUninit;
-                let mut data:[u8; DRAWPAD_W*DRAWPAD_H*4] = MaybeUninit::uninit().assume_init();
-                let mut idx = 0;
-                for y in 0..DRAWPAD_H {
-                    for x in 0..DRAWPAD_W {
-                        let c = raw[x][y];
-                        data[idx] = c.r;
-                        data[idx+1] = c.g;
-                        data[idx+2] = c.b;
-                        data[idx+3] = 0xff;
-                        idx += 4;
-                    }
-                }
-                data
+            paint(&ctx, &self.figure);
+            if let Some(mask) = &self.mask {
+                cover(&ctx, mask);
+            }
+        }
+    }
+
+    /// hands the drawing of reveal mode to the unloader, or uncovers it, as far as the step goes
+    fn sync_reveal(&mut self, state: &GameState) {
+        if let Some(Reveal { frames, schedule, step }) = &state.reveal {
+            let until = match schedule.style {
+                RevealStyle::Mask => {
+                    self.mask = Some(schedule.mask(*step)).filter(|mask|mask.contains(&true));
+                    // drawn at once, below the tiles
+                    frames.len()
+                },
+                RevealStyle::Replay => schedule.part(frames.len(), *step).end,
             };
-            if let Ok(canvas_data) = ImageData::new_with_u8_clamped_array(Clamped(&data), DRAWPAD_W as u32) {
-                ctx.put_image_data(&canvas_data, 0.0, 0.0).unwrap_or_default();
+            if until > self.revealed {
+                self.chunk_to_unload.extend_from_slice(&frames[self.revealed..until]);
+                self.revealed = until;
             }
         }
     }
@@ -131,6 +160,29 @@ impl Drawpad {
         return frame
     }
 
+    fn end_stroke(&mut self) {
+        if self.inking {
+            self.inking = false;
+            self.ink.end();
+            self.publish_ink();
+        }
+    }
+
+    fn submit_chain(&mut self) {
+        if let Some(mut frames) = self.chain_frames.take() {
+            // what was drawn since the last frame belongs to it as well
+            let frame = self.take_frame();
+            self.figure.render_frame(&frame);
+            frames.push(frame);
+            frames.retain(|frame|!frame.instructions.is_empty());
+            self.req_bus.send(Req::ChainDrawing { frames });
+        }
+    }
+
+    fn publish_ink(&mut self) {
+        self.state_agent.send(StateInput::Ink(self.ink.level()));
+    }
+
     /// the only way to push new instruction
     /// 
     fn push_instruction(&mut self, ins: Instruction) {
@@ -150,6 +202,8 @@ impl Component for Drawpad {
             color: rgb!(0,0,0),
             tool: Tool::Pencil,
             buttons:0,
+            ink: InkMeter::default(),
+            inking: false,
 
             canvas_ref: NodeRef::default(),
             figure: FigureLocal::blank(),
@@ -159,13 +213,17 @@ impl Component for Drawpad {
             frame_handle: None,
 
             stream_mode: StreamMode::Offline,
+            chain_frames: None,
+            mask: None,
+            revealed: 0,
             chunk_loader: ChunkLoader::new(),
             chunk_unloader: ChunkUnloader::new(),
             req_bus: WsReqAgent::dispatcher(),
+            state_agent: GameStateAgent::dispatcher(),
 
 
             console_bus: None,
-            resp_bus: None,
+            state_bus: None,
         }
     }
 
@@ -223,7 +281,7 @@ impl Component for Drawpad {
 
         if first_render {
             self.console_bus = Some(DrawpadAgent::bridge(ctx.link().callback(DrawpadMsg::Req)));
-            self.resp_bus = Some(WsRespAgent::bridge(ctx.link().callback(DrawpadMsg::Ws)));
+            self.state_bus = Some(GameStateAgent::bridge(ctx.link().callback(DrawpadMsg::State)));
         }
 
         if let Some(canvas) = self.get_canvas() {
@@ -261,6 +319,11 @@ impl Component for Drawpad {
                             match (self.tool, buttons) {
                                 // pencil 
                                 (Tool::Pencil, 0b00001)|(Tool::Eraser, 0b00010)  => {
+                                    if !self.ink.begin((x,y)) {
+                                        self.publish_ink();
+                                        return false
+                                    }
+                                    self.inking = true;
                                     self.push_instruction(Instruction::SetColor(self.color));
                                     self.push_instruction(Instruction::SetTool(Tool::Pencil));
                                     self.push_instruction(Instruction::PointerDown((x,y)));
@@ -282,10 +345,18 @@ impl Component for Drawpad {
                                 self.push_instruction(Instruction::PointerUp((x,y)));
                             }
                             self.buttons = 0b00000;
+                            self.end_stroke();
                         },
                         PointerAction::Move => {
-                            if (buttons == 0b00001)||(buttons == 0b00010) {
-                                self.push_instruction(Instruction::PointerMove((x,y)));
+                            if self.buttons != 0 && ((buttons == 0b00001)||(buttons == 0b00010)) {
+                                if self.inking && !self.ink.extend((x,y)) {
+                                    // out of ink, the stroke ends here
+                                    self.push_instruction(Instruction::PointerUp((x,y)));
+                                    self.buttons = 0b00000;
+                                    self.end_stroke();
+                                } else {
+                                    self.push_instruction(Instruction::PointerMove((x,y)));
+                                }
                             }
                         },
                     };
@@ -315,6 +386,12 @@ impl Component for Drawpad {
                 // render local
                 self.figure.render_frame(&frame);
 
+                if let Some(frames) = self.chain_frames.as_mut() {
+                    if !frame.instructions.is_empty() {
+                        frames.push(frame.clone());
+                    }
+                }
+
                 // in push mode, load frame
                 if self.stream_mode == StreamMode::Push {
                     // in case we get a whole chunk, send it
@@ -333,13 +410,33 @@ impl Component for Drawpad {
                 match req {
                     DrawpadReq::SetTool(t) => {self.tool = t; false},
                     DrawpadReq::SetColor(c) => {self.color = c; false},
-                    DrawpadReq::SetStreamMode(stream_mode) => {self.stream_mode = stream_mode; false},
                     DrawpadReq::Clear => {self.push_instruction(Instruction::Clear); false},
+                    DrawpadReq::SubmitChain => {self.submit_chain(); false},
                 }
             },
-            DrawpadMsg::Ws(resp) => {
-                match resp.as_ref() {
-                    Resp::Chunk { bin } => {
+            DrawpadMsg::State(StateUpdate{state, cause}) => {
+                self.stream_mode = state.stream_mode;
+                // the canvas is kept, not re-rendered, so the class is set by hand
+                if let Some(canvas) = self.get_canvas() {
+                    canvas.set_class_name(if state.is_drawing_hidden() {"hidden"} else {""});
+                }
+                if state.reveal.is_none() {
+                    self.mask = None;
+                }
+                let drawpoint = state.me().map(|me|me.drawpoint).unwrap_or(0);
+                match cause.as_deref() {
+                    Some(Resp::TurnStart(drawer)) if state.seat == Some(*drawer) => {
+                        self.ink.start_turn(drawpoint);
+                        self.publish_ink();
+                    },
+                    Some(Resp::PlayerStates(_)) if self.ink.level().is_some() => {
+                        let level = self.ink.level();
+                        self.ink.sync(drawpoint);
+                        if self.ink.level() != level {
+                            self.publish_ink();
+                        }
+                    },
+                    Some(Resp::Chunk { bin }) => {
                         if self.stream_mode == StreamMode::Receive {
                             use bincode::deserialize;
                             if let Ok(mut chunk) = deserialize::<Vec<Frame>>(bin) {
@@ -347,13 +444,46 @@ impl Component for Drawpad {
                             }
                         }
                     }
-                    Resp::Topic { topic_word:_ } => self.stream_mode = StreamMode::Push,
-                    Resp::GameStart|Resp::TurnEnd => {
+                    Some(Resp::Reveal { .. }) => {
+                        self.revealed = 0;
+                        self.chunk_to_unload.push(Frame { instructions: vec![Instruction::Reset] });
+                        self.sync_reveal(&state);
+                    },
+                    Some(Resp::RevealStep(_)) => self.sync_reveal(&state),
+                    Some(Resp::TurnEnd) => {
+                        if state.reveal.is_some() {
+                            self.sync_reveal(&state);
+                            // uncover the whole drawing, played back or not, before it is kept
+                            let rest = self.chunk_unloader.unload(Some(Vec::new()));
+                            self.figure.render_frame(&rest);
+                            for frame in std::mem::take(&mut self.chunk_to_unload) {
+                                self.figure.render_frame(&frame);
+                            }
+                            self.mask = None;
+                            self.sync_figure();
+                        }
+                        if self.ink.level().is_some() {
+                            self.ink.start_turn(0);
+                            self.publish_ink();
+                        }
+                        // keep the finished drawing for the results of the game
+                        let image = self.get_canvas().and_then(|canvas|canvas.to_data_url().ok());
+                        if let (Some(drawer), Some(image)) = (state.drawer, image) {
+                            self.state_agent.send(StateInput::Snapshot { drawer, image });
+                        }
+                        self.push_instruction(Instruction::Reset);
+                    },
+                    Some(Resp::ChainStep { task, .. }) => {
+                        self.push_instruction(Instruction::Reset);
+                        self.chain_frames = matches!(task, ChainTask::Draw{..}).then(Vec::new);
+                    },
+                    Some(Resp::ChainStepEnd) => {
+                        self.submit_chain();
+                        self.push_instruction(Instruction::Reset);
+                    },
+                    Some(Resp::GameStart|Resp::MarkEnd) => {
                         self.push_instruction(Instruction::Reset);
-                        self.stream_mode = StreamMode::Receive
                     },
-                    Resp::GameEnd => self.stream_mode = StreamMode::Offline,
-                    Resp::MarkEnd => self.push_instruction(Instruction::Reset),
                     _ => {}
                 }
                 false
@@ -0,0 +1,40 @@
+use yew::{function_component, Html, html, Properties};
+
+use dng_protocol::HintSlot;
+use crate::locals::Lang;
+
+
+#[derive(Debug, Properties, PartialEq)]
+pub struct HintBarProps {
+    pub hint: Option<Vec<HintSlot>>,
+    /// the drawer sees the whole topic instead
+    pub topic: Option<String>,
+    /// reveal mode, what guessing it now is worth
+    pub points: Option<u8>,
+    pub lang: Lang,
+}
+
+#[function_component(HintBar)]
+pub fn hint_bar(props: &HintBarProps) -> Html {
+    let local = props.lang.local();
+    let slots = match (&props.topic, &props.hint) {
+        (Some(topic), _) => HintSlot::mask(topic, &(0..topic.chars().count()).collect::<Vec<_>>()),
+        (None, Some(hint)) => hint.clone(),
+        (None, None) => return html!(<div class = "hint"></div>),
+    };
+    let length = slots.iter().filter(|s|**s != HintSlot::Gap).count();
+    let slots: Vec<Html> = slots.iter().map(|slot| match slot {
+        HintSlot::Blank => html!(<span class = "hint-slot hint-blank"></span>),
+        HintSlot::Gap => html!(<span class = "hint-gap"></span>),
+        HintSlot::Letter(c) => html!(<span class = "hint-slot"> {c} </span>),
+    }).collect();
+    html!(
+        <div class = "hint">
+            {slots}
+            <span class = "hint-length"> {local.hint_length.replace("{}", &length.to_string())} </span>
+            if let Some(points) = props.points {
+                <span class = "hint-points" title = {local.hint_points_title}> {local.hint_points.replace("{}", &points.to_string())} </span>
+            }
+        </div>
+    )
+}
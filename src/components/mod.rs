@@ -1,9 +1,14 @@
 pub mod console;
 pub mod drawpad;
+pub mod avatar;
 mod players;
 mod colorpicker;
-use std::rc::Rc;
-
+pub mod room;
+mod timer;
+mod results;
+mod hint;
+mod choice;
+mod chain;
 use yew::{Context, Component, Html, html, classes};
 use yew_agent::{/* Dispatched, Dispatcher,  */Bridge, Bridged, Dispatcher, Dispatched};
 use console::{
@@ -12,23 +17,57 @@ use console::{
     // item::{ItemKind}
 };
 use drawpad::{Drawpad, DrawpadReq, Tool};
-use players::{Players};
+use players::{Players, TeamScoreboard};
 use colorpicker::Colorpicker;
-use crate::{ws::{PlayerState, WsRespAgent, Resp}, info};
+use room::{RoomHeader, ConfigPanel};
+use timer::TurnTimerBar;
+use results::Results;
+use hint::HintBar;
+use choice::TopicChoice;
+use chain::{ChainPanel, ChainReveal};
+use std::{collections::BTreeMap, rc::Rc};
+
+use dng_protocol::{RoomState, RoomConfig, HintSlot};
+use crate::{ws::{PlayerState, WsStatusAgent, WsStatus}, info};
+use crate::locals::Lang;
+use crate::state::{GameStateAgent, StateUpdate, Guess, team_scores};
 
 use self::drawpad::DrawpadAgent;
 
 
+/// what the meters of the toolbar show
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Budget {
+    /// drawpoints of ink left and at the start of the turn
+    ink: Option<(u8, u8)>,
+    /// our timepoint against the most anyone has
+    timepoint: Option<(u16, u16)>,
+}
+
 pub struct App {
     drawpad: Dispatcher<DrawpadAgent>,
-    player_states: [Option<PlayerState>; 8],
-    drawer: u8,
+    players: Vec<PlayerState>,
+    avatars: BTreeMap<u8, Rc<str>>,
+    guesses: BTreeMap<u8, Guess>,
+    room: Option<RoomState>,
+    config: Option<RoomConfig>,
+    host: Option<u8>,
+    is_host: bool,
+    budget: Budget,
+    hint: Option<Vec<HintSlot>>,
+    topic: Option<String>,
+    reveal_points: Option<u8>,
+    drawer: Option<u8>,
     count_down: u8,
+    status: Option<WsStatus>,
+    lang: Lang,
 
-    resp_bus: Option<Box<dyn Bridge<WsRespAgent>>>,
+    state_bus: Option<Box<dyn Bridge<GameStateAgent>>>,
+    status_bus: Option<Box<dyn Bridge<WsStatusAgent>>>,
 }
 pub enum AppMsg {
-    Ws(Rc<Resp>),
+    State(StateUpdate),
+    Status(WsStatus),
     ClearButton,
     PencilButton,
     EraserButton,
@@ -41,36 +80,68 @@ impl Component for App {
     fn create(_ctx: &Context<Self>) -> Self {
         Self {
             drawpad: DrawpadAgent::dispatcher(),
-            player_states: Default::default(),
-            drawer: 0xff,
+            players: Vec::new(),
+            avatars: BTreeMap::new(),
+            guesses: BTreeMap::new(),
+            room: None,
+            config: None,
+            host: None,
+            is_host: false,
+            budget: Budget::default(),
+            hint: None,
+            topic: None,
+            reveal_points: None,
+            drawer: None,
             count_down: 00,
-            resp_bus: None
+            status: None,
+            lang: Lang::default(),
+            state_bus: None,
+            status_bus: None,
         }
     }
 
     fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
-            AppMsg::Ws(resp) => {
-                match resp.as_ref() {
-                    Resp::PlayerStates(states) => {
-                        self.player_states = Default::default();
-                        for s in states {
-                            self.player_states[s.idx as usize] = Some(s.clone());
-                        }
-                        true
-                    },
-                    Resp::CountDown(cd) => {
-                        self.count_down = *cd;
-                        true
-                    },
-                    Resp::TurnStart(drawer) => {
-                        self.drawer = *drawer;
-                        true
-                    },
-                    _ => {
-                        false
-                    }
-                }
+            AppMsg::State(StateUpdate{state, ..}) => {
+                let mut players = state.players.clone();
+                players.sort_by_key(|p|p.idx);
+                let max_timepoint = state.players.iter().map(|p|p.timepoint).max().unwrap_or(0);
+                let timepoint = state.me().map(|me|(me.timepoint, max_timepoint)).filter(|_|max_timepoint > 0);
+                let budget = Budget { ink: state.ink, timepoint };
+                let changed = players != self.players
+                    || budget != self.budget
+                    || state.avatars != self.avatars
+                    || state.guesses != self.guesses
+                    || state.drawer != self.drawer
+                    || state.count_down != self.count_down
+                    || state.room != self.room
+                    || state.config != self.config
+                    || state.host != self.host
+                    || state.is_host() != self.is_host
+                    || state.hint != self.hint
+                    || state.topic != self.topic
+                    || state.reveal_points() != self.reveal_points
+                    || state.lang != self.lang;
+                self.players = players;
+                self.budget = budget;
+                self.avatars = state.avatars.clone();
+                self.guesses = state.guesses.clone();
+                self.room = state.room.clone();
+                self.config = state.config.clone();
+                self.host = state.host;
+                self.is_host = state.is_host();
+                self.hint = state.hint.clone();
+                self.topic = state.topic.clone();
+                self.reveal_points = state.reveal_points();
+                self.drawer = state.drawer;
+                self.count_down = state.count_down;
+                self.lang = state.lang;
+                changed
+            },
+            AppMsg::Status(WsStatus::Diagnostics(_)) => false,
+            AppMsg::Status(status) => {
+                self.status = Some(status);
+                true
             },
             AppMsg::ClearButton => {self.drawpad.send(DrawpadReq::Clear);false},
             AppMsg::PencilButton => {self.drawpad.send(DrawpadReq::SetTool(Tool::Pencil));false},
@@ -79,7 +150,23 @@ impl Component for App {
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
+        let local = self.lang.local();
         let count_down = format!("{:02}", self.count_down);
+        let (latency, latency_class) = match self.status {
+            Some(WsStatus::Latency(rtt)) if rtt < 150 => (format!("{}ms", rtt), "latency-good"),
+            Some(WsStatus::Latency(rtt)) if rtt < 400 => (format!("{}ms", rtt), "latency-fair"),
+            Some(WsStatus::Latency(rtt)) => (format!("{}ms", rtt), "latency-poor"),
+            Some(WsStatus::Disconnected) => (local.latency_offline.to_string(), "latency-offline"),
+            Some(WsStatus::Diagnostics(_))|None => (local.latency_unknown.to_string(), "latency-offline"),
+        };
+        let meter = |left: f64, capacity: f64| format!("height: {:.0}%;", left * 100.0 / capacity.max(1.0));
+        let Budget { ink, timepoint } = self.budget;
+        let ink_meter = ink.map(|(left, capacity)|html!(
+            <span class="meter-drawpoint" title={format!("{}/{}", left, capacity)} style={meter(left as f64, capacity as f64)}></span>
+        )).unwrap_or_default();
+        let time_meter = timepoint.map(|(left, most)|html!(
+            <span class="meter-timepoint" title={left.to_string()} style={meter(left as f64, most as f64)}></span>
+        )).unwrap_or_default();
         let clear = ctx.link().callback(|_| {AppMsg::ClearButton});
         let pencil = ctx.link().callback(|_| {AppMsg::PencilButton});
         let eraser = ctx.link().callback(|_| {AppMsg::EraserButton});
@@ -87,15 +174,28 @@ impl Component for App {
         html! {
             <div>
                 <div class={classes!("app")}>
-                    <Players states = {self.player_states.clone()} pin = {self.drawer}/>
+                    <TeamScoreboard scores = {team_scores(&self.players)} lang = {self.lang}/>
+                    <RoomHeader room = {self.room.clone()} players = {self.players.clone()} lang = {self.lang}/>
+                    <Players states = {self.players.clone()} avatars = {self.avatars.clone()} pin = {self.drawer} host = {self.host} guesses = {self.guesses.clone()} lang = {self.lang}/>
                     <Console/>
+                    <HintBar hint = {self.hint.clone()} topic = {self.topic.clone()} points = {self.reveal_points} lang = {self.lang}/>
+                    <TurnTimerBar/>
                     <Drawpad/>
+                    <Results/>
+                    <TopicChoice/>
+                    <ChainPanel/>
+                    <ChainReveal/>
+                    <ConfigPanel config = {self.config.clone()} is_host = {self.is_host} lang = {self.lang}/>
                     <div class={classes!("toolbar")}>
                         <Colorpicker/>
-                        <div class="countdown"> {count_down}</div>
+                        <div class="countdown">
+                            <span>{count_down}</span>
+                            <span class={classes!("latency", latency_class)}>{latency}</span>
+                        </div>
                         <div id="clear-button" onclick={clear}> </div>
                         <div id="pencil-button" onclick={pencil}> </div>
                         <div id="eraser-button" onclick={eraser}> </div>
+                        <div class="budget">{ink_meter}{time_meter}</div>
                     </div>
                 </div>
                 <footer>
@@ -116,7 +216,8 @@ impl Component for App {
     fn rendered(&mut self, ctx: &Context<Self>, first_render: bool) {
         if first_render {
             info!("{}", crate::consts::WELCOME_CONSOLE);
-            self.resp_bus = Some(WsRespAgent::bridge(ctx.link().callback(AppMsg::Ws)));
+            self.state_bus = Some(GameStateAgent::bridge(ctx.link().callback(AppMsg::State)));
+            self.status_bus = Some(WsStatusAgent::bridge(ctx.link().callback(AppMsg::Status)));
         }
     }
 
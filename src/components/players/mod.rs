@@ -1,30 +1,66 @@
 
 use yew::{function_component, Html, html, Properties, classes};
 
+use std::{collections::BTreeMap, rc::Rc};
+
+use crate::locals::Lang;
 use crate::ws::PlayerState;
+use crate::state::Guess;
 
 
 #[derive(Debug, Properties, PartialEq)]
 struct PlayerItemProps {
     state: Option<PlayerState>,
+    /// drawn by the player, in place of the default one
+    avatar: Option<Rc<str>>,
     is_pinned: bool,
+    is_host: bool,
+    /// drawpoint and timepoint against the most anyone has, `None` if nobody has any
+    meters: Option<(f64, f64)>,
+    /// guessed the topic this turn
+    guess: Option<Guess>,
+    lang: Lang,
 }
 #[function_component(PlayerItem)]
 fn player_item(props: &PlayerItemProps) -> Html {
 
     // online
     if let Some(state) = &props.state {
-        let idx_id = format!("player-avatar-{}", state.idx);
+        // eight hues to go around, so larger rooms repeat them
+        let hue_class = format!("player-avatar-hue-{}", state.idx % 8);
         let ready_class = if state.ready{"player-avatar-ready"} else {"player-avatar-unready"};
+        let team_class = state.team.map(|team|format!("team-{}", team % TEAM_COLORS));
         let show_pin = if props.is_pinned{"opacity: 1;"} else {"opacity: 0;"};
+        let (custom_class, avatar_style) = match &props.avatar {
+            Some(avatar) => (Some("player-avatar-custom"), format!("background-image: url(\"{}\");", avatar)),
+            None => (None, String::new()),
+        };
         return html!(
-            <div class = "player-item">
+            <div class = {classes!("player-item", team_class)}>
                 <div class = "player-pointer" style={show_pin}></div>
                 <div class = {classes!(
                     "player-avatar-online", 
-                    ready_class
-                )} id = {idx_id}></div>
-                <div class = "player-name"> {state.name.clone()} </div>
+                    ready_class,
+                    hue_class,
+                    custom_class
+                )} style = {avatar_style}></div>
+                <div class = "player-name">
+                    if props.is_host {
+                        <span class = "player-host" title = {props.lang.local().host}> {"♛"} </span>
+                    }
+                    {state.name.clone()}
+                    if let Some(guess) = &props.guess {
+                        <span class = "player-guessed" title = {format!("#{} {}s", guess.order, guess.elapsed)}>
+                            {format!("✔+{}", guess.points)}
+                        </span>
+                    }
+                </div>
+                if let Some((drawpoint, timepoint)) = props.meters {
+                    <div class = "player-meters" title = {format!("{} / {}", state.drawpoint, state.timepoint)}>
+                        <span class = "meter-drawpoint" style = {format!("width: {:.0}%;", drawpoint * 100.0)}></span>
+                        <span class = "meter-timepoint" style = {format!("width: {:.0}%;", timepoint * 100.0)}></span>
+                    </div>
+                }
                 <div class = "score">
                     <span class = "score-voteup">       {state.score[2]} </span>{" | "}
                     <span class = "score-voteneutral">  {state.score[1]} </span>{" | "}
@@ -45,23 +81,73 @@ fn player_item(props: &PlayerItemProps) -> Html {
     }
 }   
 
+/// colors of the team classes, more teams than that share them
+const TEAM_COLORS: u8 = 4;
+
+/// seats shown even when nobody sits there, as in the rooms of eight
+const MIN_SEATS: usize = 8;
+
 #[derive(Debug, PartialEq, Properties)]
 pub struct PlayersProps {
-    pub states: [Option<PlayerState>; 8],
-    pub pin: u8
+    /// ordered by `PlayerState::idx`
+    pub states: Vec<PlayerState>,
+    /// svg data urls, from `GameState::avatars`
+    pub avatars: BTreeMap<u8, Rc<str>>,
+    pub pin: Option<u8>,
+    pub host: Option<u8>,
+    pub guesses: BTreeMap<u8, Guess>,
+    pub lang: Lang,
 }
 
 #[function_component(Players)]
 pub fn players(props: &PlayersProps) -> Html { 
-    let player_items:Vec<Html> = props.states.iter().zip(0..8).map(
-        |(state, idx)| {
-            html!(<PlayerItem state = {state.clone()} is_pinned={idx==props.pin}/>)
+    let max_drawpoint = props.states.iter().map(|s|s.drawpoint).max().unwrap_or(0);
+    let max_timepoint = props.states.iter().map(|s|s.timepoint).max().unwrap_or(0);
+    let mut player_items:Vec<Html> = props.states.iter().map(
+        |state| {
+            let meters = (max_drawpoint > 0 || max_timepoint > 0).then(|| (
+                state.drawpoint as f64 / max_drawpoint.max(1) as f64,
+                state.timepoint as f64 / max_timepoint.max(1) as f64,
+            ));
+            let avatar = props.avatars.get(&state.idx).cloned();
+            let guess = props.guesses.get(&state.idx).cloned();
+            html!(<PlayerItem key = {state.idx as u64} state = {Some(state.clone())} {avatar} is_pinned={Some(state.idx)==props.pin} is_host={Some(state.idx)==props.host} {meters} {guess} lang = {props.lang}/>)
         }
     ).collect();
+    for seat in props.states.len()..MIN_SEATS {
+        player_items.push(html!(<PlayerItem key = {format!("empty-{}", seat)} state = {None} avatar = {None} is_pinned = {false} is_host = {false} meters = {None} guess = {None} lang = {props.lang}/>));
+    }
+    let compact = (props.states.len() > MIN_SEATS).then_some("players-compact");
 
     html!(
-        <div class = {classes!("players")}>
+        <div class = {classes!("players", compact)}>
             {player_items}
         </div>
     )
-}
\ No newline at end of file
+}
+
+#[derive(Debug, PartialEq, Properties)]
+pub struct TeamScoreboardProps {
+    /// from `state::team_scores`
+    pub scores: BTreeMap<u8, i32>,
+    pub lang: Lang,
+}
+
+#[function_component(TeamScoreboard)]
+pub fn team_scoreboard(props: &TeamScoreboardProps) -> Html {
+    let best = props.scores.values().max().copied();
+    let teams: Vec<Html> = props.scores.iter().map(|(team, score)|{
+        let leading = (Some(*score) == best).then_some("team-score-leading");
+        html!(
+            <div class = {classes!("team-score", format!("team-{}", team % TEAM_COLORS), leading)}>
+                <span> {props.lang.local().team.replace("{}", &(team + 1).to_string())} </span>
+                <span> {score} </span>
+            </div>
+        )
+    }).collect();
+    html!(
+        <div class = "team-scoreboard">
+            {teams}
+        </div>
+    )
+}
@@ -0,0 +1,97 @@
+use std::rc::Rc;
+
+use yew::{Component, Context, Html, html};
+use yew_agent::{Bridge, Bridged, Dispatcher, Dispatched};
+
+use dng_protocol::Stage;
+use crate::state::{GameState, GameStateAgent, StateUpdate, TurnRecord, weighted_score};
+use crate::ws::{Req, Resp, WsReqAgent};
+
+pub enum ResultsMsg {
+    State(StateUpdate),
+    Ready,
+}
+
+/// the leaderboard shown over the drawpad when the game is over
+pub struct Results {
+    state: Rc<GameState>,
+    /// hidden after `Ready`, until the next game is over
+    dismissed: bool,
+    req_bus: Dispatcher<WsReqAgent>,
+    _state_bus: Box<dyn Bridge<GameStateAgent>>,
+}
+
+fn best_turn(turns: &[TurnRecord], idx: u8) -> Option<&TurnRecord> {
+    turns.iter()
+        .filter(|t|t.drawer == idx)
+        .max_by_key(|t|weighted_score(&t.votes))
+}
+
+impl Component for Results {
+    type Message = ResultsMsg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        Self {
+            state: Rc::new(GameState::default()),
+            dismissed: false,
+            req_bus: WsReqAgent::dispatcher(),
+            _state_bus: GameStateAgent::bridge(ctx.link().callback(ResultsMsg::State)),
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            ResultsMsg::State(StateUpdate{state, cause}) => {
+                if let Some(Resp::GameEnd) = cause.as_deref() {
+                    self.dismissed = false;
+                }
+                let changed = state.stage == Stage::Over || self.state.stage == Stage::Over;
+                self.state = state;
+                changed
+            },
+            ResultsMsg::Ready => {
+                self.req_bus.send(Req::ImReady);
+                self.dismissed = true;
+                true
+            },
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        // a telephone game is revealed chain by chain instead
+        if self.state.stage != Stage::Over || self.dismissed || !self.state.chains.is_empty() {
+            return html!();
+        }
+        let local = self.state.lang.local();
+        let mut players = self.state.players.clone();
+        players.sort_by_key(|p|(-weighted_score(&p.score), p.idx));
+        let rows: Vec<Html> = players.iter().enumerate().map(|(rank, p)| {
+            let drawing = match best_turn(&self.state.turns, p.idx).and_then(|t|t.snapshot.clone()) {
+                Some(src) => html!(<img class = "results-drawing" src = {src.to_string()}/>),
+                None => html!(<div class = "results-drawing"></div>),
+            };
+            html!(
+                <div class = "results-row">
+                    <span class = "results-rank"> {rank + 1} </span>
+                    <span class = "results-name"> {p.name.clone()} </span>
+                    <span class = "results-total"> {weighted_score(&p.score)} </span>
+                    <span class = "score">
+                        <span class = "score-voteup">       {p.score[2]} </span>{" | "}
+                        <span class = "score-voteneutral">  {p.score[1]} </span>{" | "}
+                        <span class = "score-votedown">     {p.score[0]} </span>
+                    </span>
+                    {drawing}
+                </div>
+            )
+        }).collect();
+        let ready = ctx.link().callback(|_| ResultsMsg::Ready);
+        html!(
+            <div class = "results">
+                <div class = "results-title"> {local.game_end} </div>
+                <div class = "results-table"> {rows} </div>
+                <button class = "results-ready" onclick = {ready}> {local.results_ready} </button>
+            </div>
+        )
+    }
+}
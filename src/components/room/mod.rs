@@ -0,0 +1,119 @@
+use yew::{function_component, html, Properties};
+
+use dng_protocol::{RoomState, RoomConfig, ConfigChange, Scoring, Stage, GameMode};
+use crate::locals::{Lang, Locals};
+use crate::ws::PlayerState;
+
+
+#[derive(Debug, Properties, PartialEq)]
+pub struct RoomHeaderProps {
+    pub room: Option<RoomState>,
+    pub players: Vec<PlayerState>,
+    pub lang: Lang,
+}
+
+fn player_name(players: &[PlayerState], idx: u8, local: &Locals) -> String {
+    players.iter()
+        .find(|p|p.idx == idx)
+        .map(|p|p.name.clone())
+        .unwrap_or_else(|| local.seat.replace("{}", &idx.to_string()))
+}
+
+#[function_component(RoomHeader)]
+pub fn room_header(props: &RoomHeaderProps) -> Html {
+    let local = props.lang.local();
+    if let Some(room) = &props.room {
+        let name = room.name.clone().unwrap_or_else(|| local.room_unnamed.to_string());
+        let stage = match room.stage {
+            Stage::Unready => local.stage_unready.to_string(),
+            Stage::Ready => local.stage_ready.to_string(),
+            Stage::Drawing(idx) => local.stage_drawing.replace("{}", &player_name(&props.players, idx, local)),
+            Stage::Marking(idx) => local.stage_marking.replace("{}", &player_name(&props.players, idx, local)),
+            Stage::Over => local.stage_over.to_string(),
+            Stage::Chain(step) => local.stage_chain.replace("{}", &(step + 1).to_string()),
+        };
+        let lexicon = if room.user_lexicon {
+            local.user_lexicon.to_string()
+        } else {
+            local.lexicon.replace("{}", &format!("{:08x}", room.lexicon))
+        };
+        html!(
+            <div class = "room-header">
+                <div class = "room-name"> {name} </div>
+                <div class = "room-info">
+                    <span class = "room-stage"> {stage} </span>{" | "}
+                    <span class = "room-lexicon"> {lexicon} </span>{" | "}
+                    <span class = "room-players"> {local.player_count.replace("{}", &room.playercount.to_string())} </span>
+                </div>
+            </div>
+        )
+    } else {
+        html!(<div class = "room-header"></div>)
+    }
+}
+
+/// `/config <key> <value>`
+pub fn parse_config(key: &str, value: &str) -> Option<ConfigChange> {
+    match key {
+        "rounds" => value.parse().ok().map(ConfigChange::Rounds),
+        "time" => value.parse().ok().map(ConfigChange::TurnTime),
+        "players" => value.parse().ok().map(ConfigChange::MaxPlayers),
+        "teams" => value.parse().ok().map(ConfigChange::Teams),
+        "mode" => match value {
+            "classic" => Some(ConfigChange::Mode(GameMode::Classic)),
+            "telephone" => Some(ConfigChange::Mode(GameMode::Telephone)),
+            "reveal" => Some(ConfigChange::Mode(GameMode::Reveal)),
+            _ => None,
+        },
+        "scoring" => match value {
+            "votes" => Some(ConfigChange::Scoring(Scoring::Votes)),
+            "stars" => Some(ConfigChange::Scoring(Scoring::Stars)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+pub fn describe_config(config: &RoomConfig, local: &Locals) -> String {
+    let scoring = match config.scoring {
+        Scoring::Votes => local.scoring_votes,
+        Scoring::Stars => local.scoring_stars,
+    };
+    let teams = if config.teams > 1 {
+        format!(" | {}", local.config_teams.replace("{}", &config.teams.to_string()))
+    } else {
+        String::new()
+    };
+    let mode = match config.mode {
+        GameMode::Classic => local.mode_classic,
+        GameMode::Telephone => local.mode_telephone,
+        GameMode::Reveal => local.mode_reveal,
+    };
+    format!("{} | {} | {} | {} | {}{}",
+        mode,
+        local.config_rounds.replace("{}", &config.rounds.to_string()),
+        local.config_turn_time.replace("{}", &config.turn_time.to_string()),
+        local.config_max_players.replace("{}", &config.max_players.to_string()),
+        scoring,
+        teams,
+    )
+}
+
+#[derive(Debug, Properties, PartialEq)]
+pub struct ConfigPanelProps {
+    pub config: Option<RoomConfig>,
+    pub is_host: bool,
+    pub lang: Lang,
+}
+
+#[function_component(ConfigPanel)]
+pub fn config_panel(props: &ConfigPanelProps) -> Html {
+    let local = props.lang.local();
+    let hint = if props.is_host {local.config_host_hint} else {""};
+    html!(
+        <div class = "config-panel">
+            <div> {props.config.as_ref().map(|config|describe_config(config, local)).unwrap_or_default()} </div>
+            <div class = "config-host"> {hint} </div>
+        </div>
+    )
+}
@@ -0,0 +1,78 @@
+use gloo_render::{AnimationFrame, request_animation_frame};
+use yew::{Component, Context, Html, html, classes};
+use yew_agent::{Bridge, Bridged};
+
+use crate::state::{GameStateAgent, StateUpdate, TurnTimer};
+use crate::ws::Resp;
+use dng_protocol::Stage;
+
+pub enum TimerMsg {
+    State(StateUpdate),
+    Frame,
+}
+
+/// a bar above the drawpad, draining until the turn is over
+pub struct TurnTimerBar {
+    timer: TurnTimer,
+    frame: Option<AnimationFrame>,
+    _state_bus: Box<dyn Bridge<GameStateAgent>>,
+}
+
+impl TurnTimerBar {
+    fn request_frame(&mut self, ctx: &Context<Self>) {
+        if self.frame.is_none() && self.timer.is_running() {
+            let link = ctx.link().clone();
+            self.frame = Some(request_animation_frame(move |_| link.send_message(TimerMsg::Frame)));
+        }
+    }
+}
+
+impl Component for TurnTimerBar {
+    type Message = TimerMsg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        Self {
+            timer: TurnTimer::default(),
+            frame: None,
+            _state_bus: GameStateAgent::bridge(ctx.link().callback(TimerMsg::State)),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            TimerMsg::State(StateUpdate{state, cause: Some(cause)}) => {
+                match cause.as_ref() {
+                    Resp::CountDown(cd) => {
+                        // only drawing turns last as long as the config says
+                        let length = state.config.as_ref()
+                            .map(|config|config.turn_time)
+                            .filter(|_|matches!(state.stage, Stage::Drawing(_)));
+                        self.timer.sync(*cd, length, js_sys::Date::now())
+                    },
+                    Resp::TurnStart(_)|Resp::TurnEnd|Resp::MarkStart|Resp::GameEnd => self.timer.reset(),
+                    _ => return false,
+                }
+                self.request_frame(ctx);
+                true
+            },
+            TimerMsg::State(_) => false,
+            TimerMsg::Frame => {
+                self.frame = None;
+                self.request_frame(ctx);
+                true
+            },
+        }
+    }
+
+    fn view(&self, _ctx: &Context<Self>) -> Html {
+        let now = js_sys::Date::now();
+        let width = format!("width: {:.2}%;", self.timer.fraction(now) * 100.0);
+        let warning = self.timer.is_warning(now).then_some("turn-timer-warning");
+        html!(
+            <div class = "turn-timer">
+                <div class = {classes!("turn-timer-bar", warning)} style = {width}></div>
+            </div>
+        )
+    }
+}
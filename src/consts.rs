@@ -1,5 +1,4 @@
-pub const DRAWPAD_W: usize = 128;
-pub const DRAWPAD_H: usize = 128;
+pub use dng_protocol::{DRAWPAD_W, DRAWPAD_H};
 
 pub const WELCOME_CONSOLE:&str = 
 r#"
@@ -16,8 +16,26 @@ right click little palette block to change it's color
 /ready become ready
 /unready become unready
 /name <name> set your name
+/avatar draw your avatar with the toolbar, at most 16 colors
+/config show the room settings
+/config rounds <n> set the number of rounds (host only)
+/config time <seconds> set the time of a turn (host only)
+/config players <n> set the maximum number of players (host only)
+/config scoring votes|stars score by votes or by stars (host only)
+/config teams <n> play in n teams, 0 for everyone on their own (host only)
+/config mode classic|telephone|reveal classic rounds; pass prompts, drawings and descriptions along; or guess a drawing shown bit by bit, earlier guesses score more (host only)
+/kick <name> kick a player (host only)
+/votekick <name> start a vote to kick a player
+/team switch between chatting to everyone and to your team
+/mute <name> hide the chat of a player, add canvas to hide their drawings too
+/unmute <name> show them again
 /lexicon <file-url> download lexicon from github
 /lexicon <lexicon code> set lexicon of lexicon server
+/record start record the messages from now on, nothing is recorded by default
+/record stop stop recording
+/record save download this session, open it with ?replay=<file-url>
+/record clear forget the session recorded so far
+/diag show message and error counters of the connection
 
 # Example Lexicon Repo
 https://github.com/4t145/dng-lex/tree/main/en
@@ -40,14 +58,92 @@ pub const EN:Locals = Locals {
     vote_down:    "Sucks         --p",
     vote_neutral: "Okay          o_o",
     vote_up:      "Masterpiece   ^^b",
+    average:      "average",
+    vote_kick: "Vote to kick",
+    agree: "Kick",
+    disagree: "Keep",
+    kicked: "was kicked",
+    not_kicked: "stays",
+    muted: "muted",
+    unmuted: "unmuted",
 
     key_word: "Keyword: ",
 
     unsupported: "Unsupported Command",
     check_your_input: "Please check your input",
     lack_of_parameter: "Lack of Parameter",
+    host_only: "Only the host can use this command",
+    no_such_player: "No such player",
 
+    team_chat_on: "now talking to your team",
+    team_chat_off: "now talking to everyone",
+    no_team: "you are not in a team",
+    team_placeholder: "say to your team",
+    team: "team {}",
+    team_toggle: "team",
+    team_chat_tag: "[team]",
+    record_started: "recording",
+    record_stopped: "stopped recording",
     input_placeholder: "input here",
+    latency_offline: "offline",
+    latency_unknown: "--",
+
+    diag_sent: "sent",
+    diag_received: "received",
+    diag_encode_errors: "encode errors",
+    diag_decode_errors: "decode errors",
+    diag_socket_errors: "socket errors",
+    diag_last_decode_error: "last decode error",
+    diag_last_socket_error: "last socket error",
+    diag_bytes: "{} bytes",
+
+    room_unnamed: "unnamed room",
+    stage_unready: "waiting for players",
+    stage_ready: "ready",
+    stage_drawing: "{} is drawing",
+    stage_marking: "rating {}",
+    stage_over: "game over",
+    stage_chain: "telephone step {}",
+    seat: "seat {}",
+    user_lexicon: "player lexicon",
+    lexicon: "lexicon {}",
+    player_count: "{} players",
+    mode_classic: "classic",
+    mode_telephone: "telephone",
+    mode_reveal: "reveal",
+    scoring_votes: "scored by votes",
+    scoring_stars: "scored by stars",
+    config_rounds: "{} rounds",
+    config_turn_time: "{}s a turn",
+    config_max_players: "up to {} players",
+    config_teams: "{} teams",
+    config_host_hint: "you are the host, change these with /config",
+    host: "host",
+
+    results_ready: "ready for the next game",
+
+    hint_length: "{} letters",
+    hint_points: "+{} if guessed now",
+    hint_points_title: "the earlier the guess, the more it scores",
+
+    choice_title: "choose what to draw",
+    choice_timeout: "the first one is taken in {}s",
+
+    avatar_save: "save avatar",
+    avatar_saved: "avatar saved",
+    avatar_too_many_colors: "an avatar can use 16 colors at most",
+
+    chain_progress: "step {}",
+    chain_draw: "draw: {}",
+    chain_done: "done",
+    chain_describe: "what is this drawing?",
+    chain_prompt: "write a prompt for the next player to draw",
+    chain_submit: "submit",
+    chain_submitted: "submitted",
+    chain_waiting: "submitted, waiting for the others, you can still change it until the time is up",
+    chain_owner: "prompt of {}",
+    chain_prev: "previous",
+    chain_next: "next",
 
     help: HELP
 };
\ No newline at end of file
@@ -5,6 +5,24 @@ pub use {
     en::EN,
 };
 
+/// the localization picked by `/lang`, which the components outside the console render with
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Lang {
+    #[default]
+    Zh,
+    En,
+}
+
+impl Lang {
+    pub fn local(self) -> &'static Locals<'static> {
+        match self {
+            Lang::Zh => &ZH,
+            Lang::En => &EN,
+        }
+    }
+}
+
+/// a `{}` in a string stands for its argument, put in with `str::replace`
 macro_rules! locals {
     ($struct_id:ident;$($id:ident, )*) => {
         pub struct $struct_id<'a> {
@@ -22,6 +40,8 @@ locals!{
     lack_of_parameter,
     unsupported,
     check_your_input,
+    host_only,
+    no_such_player,
 
     game_start,
     game_end,
@@ -34,9 +54,85 @@ locals!{
     vote_up,
     vote_down,
     vote_neutral,
+    average,
+    vote_kick,
+    agree,
+    disagree,
+    kicked,
+    not_kicked,
+    muted,
+    unmuted,
     key_word,
 
+    team_chat_on,
+    team_chat_off,
+    no_team,
+    team_placeholder,
+    team,
+    team_toggle,
+    team_chat_tag,
+    record_started,
+    record_stopped,
     input_placeholder,
+    latency_offline,
+    latency_unknown,
+
+    diag_sent,
+    diag_received,
+    diag_encode_errors,
+    diag_decode_errors,
+    diag_socket_errors,
+    diag_last_decode_error,
+    diag_last_socket_error,
+    diag_bytes,
+
+    room_unnamed,
+    stage_unready,
+    stage_ready,
+    stage_drawing,
+    stage_marking,
+    stage_over,
+    stage_chain,
+    seat,
+    user_lexicon,
+    lexicon,
+    player_count,
+    mode_classic,
+    mode_telephone,
+    mode_reveal,
+    scoring_votes,
+    scoring_stars,
+    config_rounds,
+    config_turn_time,
+    config_max_players,
+    config_teams,
+    config_host_hint,
+    host,
+
+    results_ready,
+
+    hint_length,
+    hint_points,
+    hint_points_title,
+
+    choice_title,
+    choice_timeout,
+
+    avatar_save,
+    avatar_saved,
+    avatar_too_many_colors,
+
+    chain_progress,
+    chain_draw,
+    chain_done,
+    chain_describe,
+    chain_prompt,
+    chain_submit,
+    chain_submitted,
+    chain_waiting,
+    chain_owner,
+    chain_prev,
+    chain_next,
 
     help,
 }
\ No newline at end of file
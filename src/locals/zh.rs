@@ -15,8 +15,26 @@ E键可以在铅笔和橡皮擦之间切换, 主要是方便数位笔使用者
 /ready 准备
 /unready 取消准备
 /name <名字> 设置名字
+/avatar 用工具栏画自己的头像，最多16种颜色
+/config 查看房间设置
+/config rounds <轮数> 设置轮数（仅房主）
+/config time <秒数> 设置每回合时长（仅房主）
+/config players <人数> 设置最大人数（仅房主）
+/config scoring votes|stars 按赞踩或星级计分（仅房主）
+/config teams <队数> 分队游戏，0为个人赛（仅房主）
+/config mode classic|telephone|reveal 经典模式；传话模式：写题目、画、描述轮流传下去；揭画模式：画一点点露出来，越早猜中得分越多（仅房主）
+/kick <名字> 踢出玩家（仅房主）
+/votekick <名字> 发起投票踢出玩家
+/team 在公共频道和队伍频道之间切换
+/mute <名字> 屏蔽玩家的聊天，加上canvas也隐藏他的画
+/unmute <名字> 取消屏蔽
 /lexicon <文件网址> 从github上下载词库
 /lexicon <词库代码> 从词库服务器设置词库
+/record start 开始记录收发的消息，默认不记录
+/record stop 停止记录
+/record save 下载本局的记录，用?replay=<文件网址>回放
+/record clear 清空目前的记录
+/diag 查看连接收发的消息和错误计数
 
 # 可用中文词库
 https://github.com/4t145/dng-lex/tree/main/zh
@@ -39,14 +57,92 @@ pub const ZH:Locals = Locals {
     vote_down: "又摆摆 --p",
     vote_neutral: "一般般 o_o",
     vote_up: "优棒棒 ^^b",
+    average: "平均",
+    vote_kick: "投票踢出",
+    agree: "同意",
+    disagree: "反对",
+    kicked: "被踢出了房间",
+    not_kicked: "没有被踢出",
+    muted: "已屏蔽",
+    unmuted: "已取消屏蔽",
 
     key_word: "关键词：",
 
     unsupported: "不支持的命令",
     check_your_input: "请检查你的输入是否正确",
     lack_of_parameter: "缺少参数",
+    host_only: "只有房主可以使用这个命令",
+    no_such_player: "没有这个玩家",
 
+    team_chat_on: "已切换到队伍频道",
+    team_chat_off: "已切换到公共频道",
+    no_team: "你不在任何队伍中",
+    team_placeholder: "对队友说",
+    team: "{}队",
+    team_toggle: "队",
+    team_chat_tag: "[队]",
+    record_started: "开始记录",
+    record_stopped: "停止记录",
     input_placeholder: "在此输入",
+    latency_offline: "离线",
+    latency_unknown: "--",
+
+    diag_sent: "已发送",
+    diag_received: "已接收",
+    diag_encode_errors: "编码错误",
+    diag_decode_errors: "解码错误",
+    diag_socket_errors: "连接错误",
+    diag_last_decode_error: "最近的解码错误",
+    diag_last_socket_error: "最近的连接错误",
+    diag_bytes: "{}字节",
+
+    room_unnamed: "未命名房间",
+    stage_unready: "等待准备",
+    stage_ready: "准备就绪",
+    stage_drawing: "{} 作画中",
+    stage_marking: "为 {} 评分",
+    stage_over: "游戏结束",
+    stage_chain: "传话第{}步",
+    seat: "{}号",
+    user_lexicon: "玩家词库",
+    lexicon: "词库 {}",
+    player_count: "{}人",
+    mode_classic: "经典模式",
+    mode_telephone: "传话模式",
+    mode_reveal: "揭画模式",
+    scoring_votes: "赞踩计分",
+    scoring_stars: "星级计分",
+    config_rounds: "{}轮",
+    config_turn_time: "每回合{}秒",
+    config_max_players: "最多{}人",
+    config_teams: "{}队",
+    config_host_hint: "你是房主，可以用/config修改",
+    host: "房主",
+
+    results_ready: "准备下一局",
+
+    hint_length: "{}字",
+    hint_points: "现在猜中+{}",
+    hint_points_title: "越早猜中得分越多",
+
+    choice_title: "选择你要画的题目",
+    choice_timeout: "{}秒后自动选择第一个",
+
+    avatar_save: "保存头像",
+    avatar_saved: "头像已保存",
+    avatar_too_many_colors: "头像最多只能用16种颜色",
+
+    chain_progress: "第{}步",
+    chain_draw: "画出：{}",
+    chain_done: "画好了",
+    chain_describe: "这画的是什么？",
+    chain_prompt: "写一个题目，交给下一位玩家来画",
+    chain_submit: "提交",
+    chain_submitted: "已提交",
+    chain_waiting: "已提交，等待其他玩家，在时间到之前还可以修改",
+    chain_owner: "{}的题目",
+    chain_prev: "上一步",
+    chain_next: "下一步",
 
     help: HELP
 };
\ No newline at end of file
@@ -4,6 +4,7 @@ mod ws;
 mod consts;
 mod utils;
 mod locals;
+mod state;
 
 
 #[global_allocator]
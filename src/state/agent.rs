@@ -0,0 +1,120 @@
+use std::{collections::HashSet, rc::Rc};
+
+use yew_agent::{Agent, AgentLink, Bridge, Bridged, Context, HandlerId};
+
+use super::{GameState, StreamMode, reduce};
+use crate::locals::Lang;
+use crate::ws::{Resp, WsRespAgent};
+
+pub enum StateInput {
+    /// `/mode`, overrides the stream mode until the next response changes it
+    SetStreamMode(StreamMode),
+    /// `/lang`, for the components outside the console
+    SetLang(Lang),
+    /// the finished drawing of `drawer`, for the results of the game
+    Snapshot {
+        drawer: u8,
+        image: String,
+    },
+    /// `/mute`, hides the chat of the player and, if `hide_drawing`, their drawings
+    Mute {
+        name: String,
+        hide_drawing: bool,
+    },
+    Unmute {
+        name: String,
+    },
+    /// the ink meter of the drawpad changed
+    Ink(Option<(u8, u8)>),
+}
+
+/// the new state, along with the response which led to it
+#[derive(Clone)]
+pub struct StateUpdate {
+    pub state: Rc<GameState>,
+    pub cause: Option<Rc<Resp>>,
+}
+
+/// holds the one `GameState` of the client and feeds every response through `reduce`
+pub struct GameStateAgent {
+    link: AgentLink<Self>,
+    state: Rc<GameState>,
+    subscribers: HashSet<HandlerId>,
+    _resp_bus: Box<dyn Bridge<WsRespAgent>>,
+}
+
+impl GameStateAgent {
+    fn broadcast(&self, cause: Option<Rc<Resp>>) {
+        let update = StateUpdate { state: self.state.clone(), cause };
+        for sub in &self.subscribers {
+            if sub.is_respondable() {
+                self.link.respond(*sub, update.clone());
+            }
+        }
+    }
+}
+
+impl Agent for GameStateAgent {
+    type Reach = Context<Self>;
+    type Message = Rc<Resp>;
+    type Input = StateInput;
+    type Output = StateUpdate;
+
+    fn create(link: AgentLink<Self>) -> Self {
+        let resp_bus = WsRespAgent::bridge(link.callback(|resp|resp));
+        Self {
+            link,
+            state: Rc::new(GameState::default()),
+            subscribers: HashSet::new(),
+            _resp_bus: resp_bus,
+        }
+    }
+
+    fn update(&mut self, resp: Self::Message) {
+        self.state = Rc::new(reduce((*self.state).clone(), &resp));
+        self.broadcast(Some(resp));
+    }
+
+    fn handle_input(&mut self, msg: Self::Input, _id: HandlerId) {
+        match msg {
+            StateInput::SetStreamMode(stream_mode) => {
+                Rc::make_mut(&mut self.state).stream_mode = stream_mode;
+                self.broadcast(None);
+            }
+            StateInput::SetLang(lang) => {
+                Rc::make_mut(&mut self.state).lang = lang;
+                self.broadcast(None);
+            }
+            StateInput::Mute { name, hide_drawing } => {
+                Rc::make_mut(&mut self.state).muted.insert(name, hide_drawing);
+                self.broadcast(None);
+            }
+            StateInput::Unmute { name } => {
+                Rc::make_mut(&mut self.state).muted.remove(&name);
+                self.broadcast(None);
+            }
+            StateInput::Ink(ink) => {
+                Rc::make_mut(&mut self.state).ink = ink;
+                self.broadcast(None);
+            }
+            StateInput::Snapshot { drawer, image } => {
+                let state = Rc::make_mut(&mut self.state);
+                if let Some(turn) = state.turns.iter_mut().rev().find(|t|t.drawer == drawer) {
+                    turn.snapshot = Some(image.into());
+                    self.broadcast(None);
+                }
+            }
+        }
+    }
+
+    fn connected(&mut self, id: HandlerId) {
+        self.subscribers.insert(id);
+        if id.is_respondable() {
+            self.link.respond(id, StateUpdate { state: self.state.clone(), cause: None });
+        }
+    }
+
+    fn disconnected(&mut self, id: HandlerId) {
+        self.subscribers.remove(&id);
+    }
+}
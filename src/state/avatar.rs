@@ -0,0 +1,16 @@
+use dng_protocol::{Avatar, AVATAR_SIZE, Color};
+
+/// the avatar as an svg data url, for css backgrounds
+pub fn to_data_url(avatar: &Avatar) -> String {
+    let mut rects = String::new();
+    for x in 0..AVATAR_SIZE {
+        for y in 0..AVATAR_SIZE {
+            let Color { r, g, b } = avatar.pixel(x, y);
+            rects.push_str(&format!("<rect x='{}' y='{}' width='1' height='1' fill='%23{:02x}{:02x}{:02x}'/>", x, y, r, g, b));
+        }
+    }
+    format!(
+        "data:image/svg+xml;utf8,<svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 {s} {s}' shape-rendering='crispEdges'>{}</svg>",
+        rects, s = AVATAR_SIZE
+    )
+}
@@ -0,0 +1,128 @@
+/// pixels of pencil stroke paid by one drawpoint
+pub const PIXELS_PER_DRAWPOINT: u32 = 32;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Budget {
+    /// the drawpoint the server last reported
+    drawpoint: u8,
+    /// pixels, at the start of the turn
+    capacity: u32,
+    /// pixels
+    left: u32,
+}
+
+/// ink of the drawer, counted locally stroke by stroke between the reports of the server
+///
+/// a server which reports no drawpoint when our turn starts sets no budget
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InkMeter {
+    budget: Option<Budget>,
+    /// end of the stroke being drawn
+    last: Option<(u8, u8)>,
+}
+
+impl InkMeter {
+    pub fn start_turn(&mut self, drawpoint: u8) {
+        let pixels = drawpoint as u32 * PIXELS_PER_DRAWPOINT;
+        self.budget = (drawpoint > 0).then_some(Budget { drawpoint, capacity: pixels, left: pixels });
+        self.last = None;
+    }
+
+    /// the server knows better whenever its report changes
+    pub fn sync(&mut self, drawpoint: u8) {
+        if let Some(budget) = &mut self.budget {
+            if budget.drawpoint != drawpoint {
+                budget.drawpoint = drawpoint;
+                budget.left = drawpoint as u32 * PIXELS_PER_DRAWPOINT;
+                budget.capacity = budget.capacity.max(budget.left);
+            }
+        }
+    }
+
+    fn spend(&mut self, pixels: u32) -> bool {
+        match &mut self.budget {
+            Some(budget) if budget.left < pixels => {
+                budget.left = 0;
+                false
+            },
+            Some(budget) => {
+                budget.left -= pixels;
+                true
+            },
+            None => true,
+        }
+    }
+
+    /// a pencil stroke starts at `coor`, false if there is no ink for it
+    pub fn begin(&mut self, coor: (u8, u8)) -> bool {
+        let ok = self.spend(1);
+        self.last = ok.then_some(coor);
+        ok
+    }
+
+    /// the stroke goes on to `coor`, false once the ink runs out and the stroke has to end
+    pub fn extend(&mut self, coor: (u8, u8)) -> bool {
+        let last = match self.last {
+            Some(last) => last,
+            None => return false,
+        };
+        // pixels of a bresenham line, not counting the one already drawn
+        let pixels = (last.0.abs_diff(coor.0)).max(last.1.abs_diff(coor.1)) as u32;
+        let ok = self.spend(pixels);
+        self.last = ok.then_some(coor);
+        ok
+    }
+
+    pub fn end(&mut self) {
+        self.last = None;
+    }
+
+    /// drawpoints left and at the start of the turn, `None` without a budget
+    pub fn level(&self) -> Option<(u8, u8)> {
+        self.budget.as_ref().map(|b| {
+            let points = |pixels: u32| pixels.div_ceil(PIXELS_PER_DRAWPOINT).min(u8::MAX as u32) as u8;
+            (points(b.left), points(b.capacity))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unlimited_without_budget() {
+        let mut ink = InkMeter::default();
+        ink.start_turn(0);
+        assert!(ink.begin((0, 0)));
+        assert!(ink.extend((127, 127)));
+        assert_eq!(ink.level(), None);
+    }
+
+    #[test]
+    fn runs_out() {
+        let mut ink = InkMeter::default();
+        ink.start_turn(1);
+        assert!(ink.begin((0, 0)));
+        assert!(ink.extend((10, 5)));
+        assert!(ink.extend((30, 5)));
+        assert_eq!(ink.level(), Some((1, 1)));
+        assert!(!ink.extend((33, 5)));
+        assert_eq!(ink.level(), Some((0, 1)));
+        ink.end();
+        assert!(!ink.begin((0, 0)));
+    }
+
+    #[test]
+    fn server_refills() {
+        let mut ink = InkMeter::default();
+        ink.start_turn(2);
+        assert!(ink.begin((0, 0)));
+        assert!(ink.extend((40, 0)));
+        // the same report again changes nothing
+        ink.sync(2);
+        assert_eq!(ink.level(), Some((1, 2)));
+        ink.sync(3);
+        assert_eq!(ink.level(), Some((3, 3)));
+    }
+}
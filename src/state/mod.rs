@@ -0,0 +1,504 @@
+mod agent;
+mod timer;
+mod ink;
+mod avatar;
+
+use std::{collections::BTreeMap, rc::Rc};
+
+use dng_protocol::{Stage, RoomState, RoomConfig, HintSlot, ChainTask, Chain, Frame, RevealSchedule};
+use crate::locals::Lang;
+use crate::ws::{Resp, PlayerState};
+
+pub use agent::{GameStateAgent, StateInput, StateUpdate};
+pub use timer::TurnTimer;
+pub use ink::InkMeter;
+pub use avatar::to_data_url;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamMode {
+    Push,
+    Receive,
+    Offline,
+}
+
+/// weights of the `[down, neutral, up]` votes in `PlayerState::score`
+pub const SCORE_WEIGHTS: [i32; 3] = [-1, 1, 2];
+
+pub fn weighted_score(score: &[u8; 3]) -> i32 {
+    score.iter().zip(SCORE_WEIGHTS).map(|(n, w)| *n as i32 * w).sum()
+}
+
+/// weighted scores of the members added up per team, empty unless the room plays in teams
+pub fn team_scores(players: &[PlayerState]) -> BTreeMap<u8, i32> {
+    let mut scores = BTreeMap::new();
+    for player in players {
+        if let Some(team) = player.team {
+            *scores.entry(team).or_insert(0) += weighted_score(&player.score);
+        }
+    }
+    scores
+}
+
+/// one turn of the game, kept until the next game starts
+#[derive(Debug, Clone, PartialEq)]
+pub struct TurnRecord {
+    pub drawer: u8,
+    /// score of the drawer when the turn started
+    pub base: [u8; 3],
+    /// votes the drawing got
+    pub votes: [u8; 3],
+    /// the finished drawing as a data url, handed in by the drawpad
+    pub snapshot: Option<Rc<str>>,
+}
+
+/// a player who guessed the topic this turn
+#[derive(Debug, Clone, PartialEq)]
+pub struct Guess {
+    pub order: u8,
+    /// seconds since the turn started
+    pub elapsed: u16,
+    pub points: u8,
+}
+
+/// the step of a telephone game everyone is at
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainStep {
+    /// counting from 0
+    pub step: u8,
+    pub steps: u8,
+    pub task: ChainTask,
+}
+
+/// reveal mode, the drawing shown to the guessers piece by piece this turn
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reveal {
+    pub frames: Rc<Vec<Frame>>,
+    pub schedule: RevealSchedule,
+    pub step: u8,
+}
+
+/// everything the client knows about the game, derived from the responses of the server
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameState {
+    pub stage: Stage,
+    pub room: Option<RoomState>,
+    pub config: Option<RoomConfig>,
+    /// our own index among the players
+    pub seat: Option<u8>,
+    pub host: Option<u8>,
+    pub players: Vec<PlayerState>,
+    /// avatars drawn by the players as svg data urls, built once when they arrive, by `PlayerState::idx`
+    pub avatars: BTreeMap<u8, Rc<str>>,
+    pub drawer: Option<u8>,
+    /// only the drawer gets to know it
+    pub topic: Option<String>,
+    /// candidates for the topic, while the drawer is picking one
+    pub topic_choices: Option<Vec<String>>,
+    /// the masked topic, for the guessers
+    pub hint: Option<Vec<HintSlot>>,
+    pub stream_mode: StreamMode,
+    pub lang: Lang,
+    pub count_down: u8,
+    pub turns: Vec<TurnRecord>,
+    /// players muted by `/mute`, by name, and whether their drawings are hidden as well
+    pub muted: BTreeMap<String, bool>,
+    /// drawpoints of ink left and at the start of the turn, while we draw with a budget
+    pub ink: Option<(u8, u8)>,
+    /// who guessed the topic this turn, by `PlayerState::idx`
+    pub guesses: BTreeMap<u8, Guess>,
+    /// telephone mode, until the step ends
+    pub chain_step: Option<ChainStep>,
+    /// telephone mode, revealed at the end of the game
+    pub chains: Vec<Chain>,
+    /// reveal mode, from the start of the turn
+    pub reveal: Option<Reveal>,
+}
+
+impl Default for GameState {
+    fn default() -> Self {
+        Self {
+            stage: Stage::Unready,
+            room: None,
+            config: None,
+            seat: None,
+            host: None,
+            players: Vec::new(),
+            avatars: BTreeMap::new(),
+            drawer: None,
+            topic: None,
+            topic_choices: None,
+            hint: None,
+            stream_mode: StreamMode::Offline,
+            lang: Lang::default(),
+            count_down: 0,
+            turns: Vec::new(),
+            muted: BTreeMap::new(),
+            ink: None,
+            guesses: BTreeMap::new(),
+            chain_step: None,
+            chains: Vec::new(),
+            reveal: None,
+        }
+    }
+}
+
+impl GameState {
+    pub fn is_host(&self) -> bool {
+        self.seat.is_some() && self.seat == self.host
+    }
+
+    pub fn me(&self) -> Option<&PlayerState> {
+        self.seat.and_then(|seat|self.players.iter().find(|p|p.idx == seat))
+    }
+
+    pub fn player_by_name(&self, name: &str) -> Option<&PlayerState> {
+        self.players.iter().find(|p|p.name == name)
+    }
+
+    /// reveal mode, the points guessing the topic is worth now, unless we can not guess any more
+    pub fn reveal_points(&self) -> Option<u8> {
+        let guessing = matches!(self.stage, Stage::Drawing(drawer) if self.seat != Some(drawer))
+            && !self.seat.is_some_and(|seat|self.guesses.contains_key(&seat));
+        self.reveal.as_ref()
+            .filter(|_|guessing)
+            .map(|reveal|reveal.schedule.points_at(reveal.step))
+    }
+
+    /// the drawer is muted along with their drawings
+    pub fn is_drawing_hidden(&self) -> bool {
+        self.drawer
+            .and_then(|idx|self.players.iter().find(|p|p.idx == idx))
+            .and_then(|p|self.muted.get(&p.name))
+            .copied()
+            .unwrap_or(false)
+    }
+}
+
+/// the state after `resp`, the only place where the game state changes
+pub fn reduce(mut state: GameState, resp: &Resp) -> GameState {
+    match resp {
+        Resp::PlayerStates(players) => {
+            state.players = players.clone();
+            if let Stage::Unready|Stage::Ready = state.stage {
+                let all_ready = !players.is_empty() && players.iter().all(|p|p.ready);
+                state.stage = if all_ready {Stage::Ready} else {Stage::Unready};
+            }
+            if let (Stage::Drawing(drawer)|Stage::Marking(drawer), Some(turn)) = (state.stage, state.turns.last_mut()) {
+                if let Some(p) = players.iter().find(|p|p.idx == drawer && turn.drawer == drawer) {
+                    for i in 0..3 {
+                        turn.votes[i] = p.score[i].saturating_sub(turn.base[i]);
+                    }
+                }
+            }
+        },
+        Resp::GameStart => {
+            state.stage = Stage::Ready;
+            state.stream_mode = StreamMode::Receive;
+            state.turns.clear();
+            state.chains.clear();
+            state.reveal = None;
+        },
+        Resp::TurnStart(drawer) => {
+            state.stage = Stage::Drawing(*drawer);
+            state.drawer = Some(*drawer);
+            state.topic = None;
+            state.hint = None;
+            state.guesses.clear();
+            state.reveal = None;
+            let base = state.players.iter().find(|p|p.idx == *drawer).map(|p|p.score).unwrap_or_default();
+            state.turns.push(TurnRecord { drawer: *drawer, base, votes: [0; 3], snapshot: None });
+        },
+        Resp::TopicChoices { candidates, .. } => {
+            state.topic_choices = Some(candidates.clone());
+        },
+        Resp::Topic { topic_word } => {
+            state.topic = Some(topic_word.clone());
+            state.topic_choices = None;
+            state.stream_mode = StreamMode::Push;
+        },
+        Resp::TurnEnd => {
+            state.stream_mode = StreamMode::Receive;
+            state.topic_choices = None;
+            // nothing is left to guess, so nothing is left to hide
+            if let Some(reveal) = state.reveal.as_mut() {
+                reveal.step = reveal.schedule.steps;
+            }
+        },
+        Resp::MarkStart => {
+            if let Some(drawer) = state.drawer {
+                state.stage = Stage::Marking(drawer);
+            }
+        },
+        Resp::GameEnd => {
+            state.stage = Stage::Over;
+            state.drawer = None;
+            state.topic = None;
+            state.hint = None;
+            state.topic_choices = None;
+            state.chain_step = None;
+            state.reveal = None;
+            state.stream_mode = StreamMode::Offline;
+        },
+        Resp::CountDown(cd) => {
+            state.count_down = *cd;
+        },
+        Resp::Guessed { idx, order, elapsed, points } => {
+            state.guesses.insert(*idx, Guess { order: *order, elapsed: *elapsed, points: *points });
+        },
+        Resp::Seat(idx) => {
+            state.seat = Some(*idx);
+        },
+        Resp::Host(idx) => {
+            state.host = Some(*idx);
+        },
+        Resp::RoomConfig(config) => {
+            state.config = Some(config.clone());
+        },
+        Resp::Avatar { idx, avatar } => {
+            state.avatars.insert(*idx, to_data_url(avatar).into());
+        },
+        Resp::Hint { slots } => {
+            state.hint = Some(slots.clone());
+        },
+        Resp::ChainStep { step, steps, task, .. } => {
+            state.stage = Stage::Chain(*step);
+            state.chain_step = Some(ChainStep { step: *step, steps: *steps, task: task.clone() });
+            // everyone draws on their own, nothing is streamed
+            state.stream_mode = StreamMode::Offline;
+        },
+        Resp::ChainStepEnd => {
+            state.chain_step = None;
+        },
+        Resp::ChainReveal { chains } => {
+            state.chains = chains.clone();
+        },
+        Resp::Reveal { frames, schedule } => {
+            state.reveal = Some(Reveal { frames: Rc::new(frames.clone()), schedule: schedule.clone(), step: 0 });
+            // the drawing is played through the drawpad like a streamed one
+            state.stream_mode = StreamMode::Receive;
+        },
+        Resp::RevealStep(step) => {
+            if let Some(reveal) = state.reveal.as_mut() {
+                reveal.step = *step;
+            }
+        },
+        Resp::RoomState(room) => {
+            // the stage of the server wins over the one we guessed
+            state.stage = room.stage;
+            state.room = Some(room.clone());
+        },
+        _ => {}
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(idx: u8, ready: bool) -> PlayerState {
+        PlayerState { name: format!("p{}", idx), idx, ready, score: [0;3], drawpoint: 0, timepoint: 0, team: None }
+    }
+
+    fn run(resps: &[Resp]) -> GameState {
+        resps.iter().fold(GameState::default(), reduce)
+    }
+
+    #[test]
+    fn lobby_is_ready_when_everyone_is() {
+        let state = run(&[Resp::PlayerStates(vec![player(0, true), player(1, false)])]);
+        assert_eq!(state.stage, Stage::Unready);
+        let state = reduce(state, &Resp::PlayerStates(vec![player(0, true), player(1, true)]));
+        assert_eq!(state.stage, Stage::Ready);
+        assert_eq!(state.players.len(), 2);
+    }
+
+    #[test]
+    fn guesser_turn() {
+        let state = run(&[Resp::GameStart, Resp::TurnStart(2), Resp::CountDown(42)]);
+        assert_eq!(state.stage, Stage::Drawing(2));
+        assert_eq!(state.drawer, Some(2));
+        assert_eq!(state.stream_mode, StreamMode::Receive);
+        assert_eq!(state.count_down, 42);
+        let state = run(&[Resp::GameStart, Resp::TurnStart(2), Resp::TurnEnd, Resp::MarkStart]);
+        assert_eq!(state.stage, Stage::Marking(2));
+    }
+
+    #[test]
+    fn drawer_turn() {
+        let state = run(&[Resp::GameStart, Resp::TurnStart(0), Resp::Topic { topic_word: "苹果".into() }]);
+        assert_eq!(state.stream_mode, StreamMode::Push);
+        assert_eq!(state.topic.as_deref(), Some("苹果"));
+        let state = reduce(state, &Resp::TurnEnd);
+        assert_eq!(state.stream_mode, StreamMode::Receive);
+        let state = reduce(state, &Resp::TurnStart(1));
+        assert_eq!(state.topic, None);
+    }
+
+    #[test]
+    fn topic_choices_until_picked() {
+        let choices = Resp::TopicChoices { candidates: vec!["苹果".into(), "香蕉".into()], timeout: 10 };
+        let state = run(&[Resp::GameStart, Resp::TurnStart(0), choices]);
+        assert_eq!(state.topic_choices.as_ref().map(Vec::len), Some(2));
+        let state = reduce(state, &Resp::Topic { topic_word: "香蕉".into() });
+        assert_eq!(state.topic_choices, None);
+        assert_eq!(state.stream_mode, StreamMode::Push);
+    }
+
+    #[test]
+    fn hint_lasts_one_turn() {
+        let slots = HintSlot::mask("苹果", &[1]);
+        let state = run(&[Resp::GameStart, Resp::TurnStart(2), Resp::Hint { slots: slots.clone() }]);
+        assert_eq!(state.hint, Some(slots));
+        let state = reduce(state, &Resp::TurnStart(3));
+        assert_eq!(state.hint, None);
+    }
+
+    #[test]
+    fn room_state_sets_the_stage() {
+        let room = RoomState { name: None, stage: Stage::Drawing(3), playercount: 4, user_lexicon: true, lexicon: 0 };
+        let state = run(&[Resp::RoomState(room.clone())]);
+        assert_eq!(state.stage, Stage::Drawing(3));
+        assert_eq!(state.room, Some(room));
+    }
+
+    #[test]
+    fn votes_of_a_turn() {
+        let mut drawer = player(1, true);
+        drawer.score = [1, 0, 2];
+        let state = run(&[Resp::PlayerStates(vec![player(0, true), drawer.clone()]), Resp::GameStart, Resp::TurnStart(1)]);
+        assert_eq!(state.turns.len(), 1);
+        assert_eq!(state.turns[0].base, [1, 0, 2]);
+        drawer.score = [1, 1, 5];
+        let state = [Resp::TurnEnd, Resp::MarkStart, Resp::MarkEnd, Resp::PlayerStates(vec![player(0, true), drawer])]
+            .iter().fold(state, reduce);
+        assert_eq!(state.turns[0].votes, [0, 1, 3]);
+        assert_eq!(weighted_score(&state.turns[0].votes), 7);
+        let state = reduce(state, &Resp::GameStart);
+        assert!(state.turns.is_empty());
+    }
+
+    #[test]
+    fn team_scores_add_up() {
+        let member = |idx, team, score| PlayerState { team, score, ..player(idx, true) };
+        let players = [
+            member(0, Some(0), [0, 1, 2]),
+            member(1, Some(1), [1, 0, 0]),
+            member(2, Some(0), [0, 0, 1]),
+            member(3, None, [0, 0, 9]),
+        ];
+        let scores = team_scores(&players);
+        assert_eq!(scores.into_iter().collect::<Vec<_>>(), vec![(0, 7), (1, -1)]);
+        assert!(team_scores(&players[3..]).is_empty());
+    }
+
+    #[test]
+    fn telephone_steps_and_reveal() {
+        use dng_protocol::{ChainLink, ChainEntry};
+        let state = run(&[
+            Resp::GameStart,
+            Resp::ChainStep { step: 0, steps: 3, task: ChainTask::Prompt, timeout: 30 },
+        ]);
+        assert_eq!(state.stage, Stage::Chain(0));
+        assert_eq!(state.stream_mode, StreamMode::Offline);
+        assert_eq!(state.chain_step.as_ref().map(|s|&s.task), Some(&ChainTask::Prompt));
+
+        let draw = ChainTask::Draw { prompt: "会飞的猫".into() };
+        let state = [Resp::ChainStepEnd, Resp::ChainStep { step: 1, steps: 3, task: draw.clone(), timeout: 60 }]
+            .iter().fold(state, reduce);
+        assert_eq!(state.stage, Stage::Chain(1));
+        assert_eq!(state.chain_step, Some(ChainStep { step: 1, steps: 3, task: draw }));
+
+        let chain = Chain { owner: 0, links: vec![ChainLink { author: 0, entry: ChainEntry::Text("会飞的猫".into()) }] };
+        let state = [Resp::ChainStepEnd, Resp::ChainReveal { chains: vec![chain.clone()] }, Resp::GameEnd]
+            .iter().fold(state, reduce);
+        assert_eq!(state.chain_step, None);
+        assert_eq!(state.chains, vec![chain]);
+        assert_eq!(state.stage, Stage::Over);
+        assert!(reduce(state, &Resp::GameStart).chains.is_empty());
+    }
+
+    #[test]
+    fn reveal_steps_and_points() {
+        use dng_protocol::RevealStyle;
+        let schedule = RevealSchedule { style: RevealStyle::Replay, steps: 3, interval: 10, seed: 1, points: vec![9, 6, 3] };
+        let state = run(&[
+            Resp::Seat(1),
+            Resp::GameStart,
+            Resp::TurnStart(0),
+            Resp::Reveal { frames: vec![Frame::new(); 6], schedule },
+        ]);
+        assert_eq!(state.stream_mode, StreamMode::Receive);
+        assert_eq!(state.reveal_points(), Some(9));
+        let state = reduce(state, &Resp::RevealStep(2));
+        assert_eq!(state.reveal.as_ref().map(|r|r.step), Some(2));
+        assert_eq!(state.reveal_points(), Some(3));
+
+        // guessed, or drawing, there is nothing to win
+        let guessed = reduce(state.clone(), &Resp::Guessed { idx: 1, order: 1, elapsed: 25, points: 3 });
+        assert_eq!(guessed.reveal_points(), None);
+        assert_eq!(reduce(state.clone(), &Resp::Seat(0)).reveal_points(), None);
+
+        let state = reduce(state, &Resp::TurnEnd);
+        assert_eq!(state.reveal.as_ref().map(|r|r.step), Some(3));
+        assert_eq!(reduce(state, &Resp::TurnStart(1)).reveal, None);
+    }
+
+    #[test]
+    fn guesses_last_one_turn() {
+        let state = run(&[
+            Resp::GameStart,
+            Resp::TurnStart(0),
+            Resp::Guessed { idx: 2, order: 1, elapsed: 12, points: 8 },
+            Resp::Guessed { idx: 1, order: 2, elapsed: 30, points: 5 },
+        ]);
+        assert_eq!(state.guesses.len(), 2);
+        assert_eq!(state.guesses[&1].order, 2);
+        let state = reduce(state, &Resp::TurnStart(1));
+        assert!(state.guesses.is_empty());
+    }
+
+    #[test]
+    fn host() {
+        let state = run(&[Resp::Host(0)]);
+        assert!(!state.is_host());
+        let state = reduce(state, &Resp::Seat(0));
+        assert!(state.is_host());
+        let state = reduce(state, &Resp::Host(1));
+        assert!(!state.is_host());
+    }
+
+    #[test]
+    fn hidden_drawing() {
+        let mut state = run(&[Resp::PlayerStates(vec![player(0, true), player(1, true)]), Resp::GameStart, Resp::TurnStart(1)]);
+        state.muted.insert("p1".into(), false);
+        assert!(!state.is_drawing_hidden());
+        state.muted.insert("p1".into(), true);
+        assert!(state.is_drawing_hidden());
+        let state = reduce(state, &Resp::TurnStart(0));
+        assert!(!state.is_drawing_hidden());
+    }
+
+    #[test]
+    fn avatars_by_player() {
+        let avatar = |palette| dng_protocol::Avatar { palette, pixels: vec![0; 128] };
+        let state = run(&[
+            Resp::Avatar { idx: 1, avatar: avatar(vec![]) },
+            Resp::Avatar { idx: 1, avatar: avatar(vec![dng_protocol::Color::white()]) },
+        ]);
+        assert_eq!(state.avatars.len(), 1);
+        assert!(state.avatars[&1].starts_with("data:image/svg+xml"));
+        assert!(state.avatars[&1].contains("%23ffffff"));
+    }
+
+    #[test]
+    fn game_over() {
+        let state = run(&[Resp::GameStart, Resp::TurnStart(0), Resp::GameEnd]);
+        assert_eq!(state.stage, Stage::Over);
+        assert_eq!(state.drawer, None);
+        assert_eq!(state.stream_mode, StreamMode::Offline);
+        // a finished game does not go back to the lobby on its own
+        let state = reduce(state, &Resp::PlayerStates(vec![player(0, false)]));
+        assert_eq!(state.stage, Stage::Over);
+    }
+}
@@ -0,0 +1,104 @@
+/// seconds left at which the timer turns into a warning
+pub const WARNING_SECS: f64 = 5.0;
+
+/// a countdown which runs smoothly between the `CountDown` ticks of the server
+///
+/// the server only sends whole seconds, the timer counts down from the last one by itself
+/// and snaps back whenever a new one arrives
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TurnTimer {
+    /// the length of the turn, or the longest countdown seen when the room config does not tell
+    total: u8,
+    value: u8,
+    /// `Date.now()` when `value` arrived
+    synced_at: f64,
+}
+
+impl TurnTimer {
+    /// a new turn (or marking) begins
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// an authoritative `CountDown` from the server, `length` is the turn time of the room config if known
+    pub fn sync(&mut self, value: u8, length: Option<u8>, now: f64) {
+        self.total = match length {
+            Some(length) => length.max(value),
+            None => self.total.max(value),
+        };
+        self.value = value;
+        self.synced_at = now;
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.value > 0
+    }
+
+    /// seconds left, never running ahead of the next tick of the server
+    pub fn remaining(&self, now: f64) -> f64 {
+        let elapsed = ((now - self.synced_at) / 1000.0).max(0.0);
+        let floor = self.value.saturating_sub(1) as f64;
+        (self.value as f64 - elapsed).max(floor)
+    }
+
+    /// how much of the turn is left, from 1 down to 0
+    pub fn fraction(&self, now: f64) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.remaining(now) / self.total as f64
+        }
+    }
+
+    pub fn is_warning(&self, now: f64) -> bool {
+        self.is_running() && self.remaining(now) <= WARNING_SECS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interpolates_between_ticks() {
+        let mut timer = TurnTimer::default();
+        timer.sync(60, None, 0.0);
+        assert_eq!(timer.fraction(0.0), 1.0);
+        assert_eq!(timer.remaining(500.0), 59.5);
+        // a late tick does not let the timer run ahead
+        assert_eq!(timer.remaining(3000.0), 59.0);
+    }
+
+    #[test]
+    fn snaps_to_the_server() {
+        let mut timer = TurnTimer::default();
+        timer.sync(60, None, 0.0);
+        timer.sync(30, None, 1000.0);
+        assert_eq!(timer.remaining(1000.0), 30.0);
+        assert_eq!(timer.fraction(1000.0), 0.5);
+        assert!(!timer.is_warning(1000.0));
+        timer.sync(5, None, 2000.0);
+        assert!(timer.is_warning(2000.0));
+    }
+
+    #[test]
+    fn reset_forgets_the_length() {
+        let mut timer = TurnTimer::default();
+        timer.sync(60, None, 0.0);
+        timer.reset();
+        timer.sync(20, None, 0.0);
+        assert_eq!(timer.fraction(0.0), 1.0);
+        timer.sync(0, None, 0.0);
+        assert!(!timer.is_running());
+        assert!(!timer.is_warning(0.0));
+    }
+
+    #[test]
+    fn joining_mid_turn_uses_the_turn_time() {
+        let mut timer = TurnTimer::default();
+        timer.sync(30, Some(60), 0.0);
+        assert_eq!(timer.fraction(0.0), 0.5);
+        timer.sync(29, Some(60), 1000.0);
+        assert_eq!(timer.fraction(1000.0), 29.0 / 60.0);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{components::drawpad::Color, rgb};
+use dng_protocol::{Color, rgb};
 
 pub fn parse_color<'a>(s: impl Into<&'a str>) -> Option<Color> {
     let s:&str = s.into();
@@ -16,4 +16,27 @@ macro_rules! info {
     ($($arg:tt)*) => {
         web_sys::console::log_1(&wasm_bindgen::JsValue::from_str(format!($($arg)*).as_str()));
     };
-}
\ No newline at end of file
+}
+
+/// read a parameter of the page url, like `server` in `?server=127.0.0.1:9000`
+pub fn url_param(key: &str) -> Option<String> {
+    let search = web_sys::window()?.location().search().ok()?;
+    web_sys::UrlSearchParams::new_with_str(search.as_str()).ok()?.get(key)
+}
+
+/// let the browser save `content` as a file named `filename`
+pub fn download(filename: &str, content: &str, mime: &str) -> Option<()> {
+    use wasm_bindgen::JsCast;
+    let parts = js_sys::Array::of1(&wasm_bindgen::JsValue::from_str(content));
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type(mime);
+    let blob = web_sys::Blob::new_with_str_sequence_and_options(&parts, &options).ok()?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob).ok()?;
+    let anchor = web_sys::window()?.document()?
+        .create_element("a").ok()?
+        .dyn_into::<web_sys::HtmlAnchorElement>().ok()?;
+    anchor.set_href(url.as_str());
+    anchor.set_download(filename);
+    anchor.click();
+    web_sys::Url::revoke_object_url(url.as_str()).ok()
+}
@@ -0,0 +1,31 @@
+use dng_protocol::{PROTOCOL_VERSION, CAP_HEARTBEAT, CAP_STARS};
+
+use super::Req;
+
+/// optional features this client supports, only those both sides announce are used
+pub const CAPABILITIES: u32 = CAP_HEARTBEAT | CAP_STARS;
+
+/// compare the version announced by the server with ours
+pub fn check(version: u16) -> Result<(), String> {
+    if version > PROTOCOL_VERSION {
+        Err(format!("客户端版本过旧(协议v{}, 服务器v{})，请刷新页面或更新客户端", PROTOCOL_VERSION, version))
+    } else if version < PROTOCOL_VERSION {
+        Err(format!("服务器版本过旧(协议v{}, 客户端v{})，请联系房主更新服务器", version, PROTOCOL_VERSION))
+    } else {
+        Ok(())
+    }
+}
+
+/// rewrite `req` into something a server with only `capabilities` understands
+pub fn downgrade(req: Req, capabilities: u32) -> Req {
+    match req {
+        Req::Rate { stars } if capabilities & CAP_STARS == 0 => Req::Mark {
+            score: match stars {
+                0..=2 => -1,
+                3 => 0,
+                _ => 1,
+            }
+        },
+        req => req,
+    }
+}
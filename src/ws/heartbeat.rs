@@ -0,0 +1,59 @@
+/// ms between two pings
+pub const HEARTBEAT_INTERVAL: u32 = 5000;
+/// pings left unanswered before the connection is considered dead
+pub const MAX_MISSED: u8 = 3;
+
+/// what to do on a tick of the heartbeat
+pub enum Beat {
+    /// send a ping with this stamp
+    Ping(u32),
+    /// the last ping is still on its way, give it another interval
+    Wait,
+    /// too many intervals without a pong
+    Dead,
+}
+
+/// keepalive bookkeeping of one connection
+///
+/// the stamp of a ping is the ms elapsed since the connection was opened,
+/// the server echoes it back in a pong so the round trip time needs no other state
+pub struct Heartbeat {
+    start: f64,
+    pending: Option<u32>,
+    missed: u8,
+}
+
+impl Heartbeat {
+    pub fn new() -> Self {
+        Self {
+            start: js_sys::Date::now(),
+            pending: None,
+            missed: 0,
+        }
+    }
+
+    fn now(&self) -> u32 {
+        (js_sys::Date::now() - self.start) as u32
+    }
+
+    /// called every `HEARTBEAT_INTERVAL`, only one ping is on its way at a time
+    /// so a late pong still matches it
+    pub fn tick(&mut self) -> Beat {
+        if self.pending.is_some() {
+            self.missed += 1;
+            return if self.missed >= MAX_MISSED {Beat::Dead} else {Beat::Wait};
+        }
+        let stamp = self.now();
+        self.pending = Some(stamp);
+        Beat::Ping(stamp)
+    }
+
+    /// any pong proves the connection alive, returns the round trip time in ms
+    pub fn pong(&mut self, stamp: u32) -> u32 {
+        if self.pending == Some(stamp) {
+            self.pending = None;
+        }
+        self.missed = 0;
+        self.now().saturating_sub(stamp)
+    }
+}
@@ -1,67 +1,235 @@
-
 use yew_agent::{Dispatched};
-use gloo_net::websocket::{Message, futures::WebSocket};
 use wasm_bindgen_futures::spawn_local;
-use futures::{channel::mpsc::Sender, SinkExt, StreamExt};
-use web_sys::{window, UrlSearchParams};
-use bincode::{serialize, deserialize};
+use futures::{channel::mpsc::Sender, SinkExt, StreamExt, select};
+use gloo_timers::future::IntervalStream;
+use web_sys::window;
+use std::{cell::{Cell, RefCell}, rc::Rc};
 
 
-mod request;
-mod response;
 mod agent;
+mod heartbeat;
+mod status;
+mod handshake;
+mod recorder;
+pub mod transport;
 
-pub use request::PlayerRequest as Req;
-pub use response::{PlayerResponse as Resp, PlayerState};
+pub use dng_protocol::{PlayerRequest as Req, PlayerResponse as Resp, PlayerState};
 pub use agent::{WsRespAgent, WsReqAgent};
+pub use status::{WsStatus, WsStatusAgent, Diagnostics};
+pub use dng_protocol::PROTOCOL_VERSION;
+pub use handshake::CAPABILITIES;
+pub use recorder::{RecorderAgent, RecorderInput};
+
+use heartbeat::{Beat, Heartbeat, HEARTBEAT_INTERVAL};
+use dng_protocol::{CAP_HEARTBEAT, CAP_JSON};
+use transport::{Transport, TransportError, WebSocketTransport, WireFormat, ScriptedServer, NetCondition, Simulated, Replay, loopback};
 
 use crate::components::console::{agent::ConsoleAgent, item::ItemKind};
+use crate::{utils::url_param, info};
 
+const DEFAULT_PATH: &str = "/login";
+
+/// build the websocket endpoint from the `server` url parameter
+///
+/// a full `ws://` or `wss://` url is used as it is, otherwise `server` is taken as
+/// `host:port` (optionally followed by a path prefix) and the scheme follows the page,
+/// so a page served over https connects with `wss`
+pub fn endpoint(server: &str, path: Option<&str>, secure: bool) -> String {
+    if server.starts_with("ws://") || server.starts_with("wss://") {
+        return server.to_string();
+    }
+    let scheme = if secure {"wss"} else {"ws"};
+    let path = path.unwrap_or(DEFAULT_PATH).trim_start_matches('/');
+    format!("{}://{}/{}", scheme, server.trim_end_matches('/'), path)
+}
 
-pub fn ws_service_init() -> Option<Sender<request::PlayerRequest>> {
+pub fn ws_service_init() -> Option<Sender<Req>> {
     let mut console = ConsoleAgent::dispatcher();
-    if let Some(window) = window() {
-    if let Ok(url) = window.location().search() {
-    if let Ok(urlsearch) = UrlSearchParams::new_with_str(url.as_str()) {
-    if let Some(server) = urlsearch.get("server") {
-        if let Ok(ws) = WebSocket::open(format!("ws://{}/login", server).as_str()) {
-            let (mut ws_tx, mut ws_rx) = ws.split();
+    // `?replay=<url>` plays back a session saved by `/record save`
+    if let Some(replay) = url_param("replay") {
+        console.send(ItemKind::GameState{msg: format!("回放{}", replay)});
+        return Some(serve(Replay::new(replay.as_str())));
+    }
+    // `?script=demo` plays a built-in game against an in-memory server
+    if let Some(script) = url_param("script") {
+        return match script.as_str() {
+            "demo" => {
+                let (client, server) = loopback();
+                spawn_local(ScriptedServer::demo().run(server));
+                console.send(ItemKind::GameState{msg: format!("已连接至脚本{}", script)});
+                Some(start(client))
+            }
+            _ => {
+                console.send(ItemKind::Warn{msg: format!("没有脚本{}", script)});
+                None
+            }
+        }
+    }
+    if let Some(server) = url_param("server") {
+        let secure = window()
+            .and_then(|w|w.location().protocol().ok())
+            .map(|p|p == "https:")
+            .unwrap_or(false);
+        let url = endpoint(server.as_str(), url_param("path").as_deref(), secure);
+        // `?format=json` asks for readable text frames
+        let format = match url_param("format").as_deref() {
+            Some("json") => WireFormat::Json,
+            _ => WireFormat::Bincode,
+        };
+        return match WebSocketTransport::open(url.as_str(), format) {
+            Ok(ws) => {
+                console.send(ItemKind::GameState{msg: format!("已连接至服务器{}", url)});
+                Some(start(ws))
+            }
+            Err(_) => {
+                console.send(ItemKind::Warn{msg: format!("无法连接至服务器{}", url)});
+                None
+            }
+        }
+    }
+    console.send(ItemKind::Warn{msg: "url缺少参数server".to_string()});
+    None
+}
 
-            let (req_tx, mut req_rx) = futures::channel::mpsc::channel::<request::PlayerRequest>(64);
+/// `?netsim=...` puts a simulated bad network in front of the transport
+fn start(transport: impl Transport + 'static) -> Sender<Req> {
+    let mut console = ConsoleAgent::dispatcher();
+    match url_param("netsim") {
+        Some(netsim) => match NetCondition::parse(netsim.as_str()) {
+            Some(cond) => {
+                console.send(ItemKind::Warn{msg: format!("网络模拟: {}", cond)});
+                serve(Simulated::new(transport, cond))
+            }
+            None => {
+                console.send(ItemKind::Warn{msg: format!("无法解析参数netsim: {}", netsim)});
+                serve(transport)
+            }
+        }
+        None => serve(transport)
+    }
+}
 
-            spawn_local(async move {
-                while let Some(req) = req_rx.next().await {
-                    if let Ok(bin) = serialize(&req) {
-                        ws_tx.send(Message::Bytes(bin)).await.unwrap_or_default();
-                    }
-                }
-            });
+/// pump requests into the transport and responses out to `WsRespAgent`,
+/// after a version handshake which decides whether to keep the connection alive with a heartbeat
+fn serve(transport: impl Transport) -> Sender<Req> {
+    let ours = CAPABILITIES | transport.capabilities();
+    let (mut tx, rx) = transport.split();
 
-            spawn_local(async move {
-                let mut dispatcher =  agent::WsRespAgent::dispatcher();
-                while let Some(msg) = ws_rx.next().await {
-                    match msg {
-                        Ok(Message::Text(_)) => {}
-                        Ok(Message::Bytes(bin)) => {
-                            if let Ok(resp) = deserialize::<Resp>(&bin) {
-                                dispatcher.send(resp)
-                            }
+    let (mut req_tx, mut req_rx) = futures::channel::mpsc::channel::<Req>(64);
+    req_tx.try_send(Req::Hello { version: PROTOCOL_VERSION, capabilities: ours }).unwrap_or_default();
+    if let Some(avatar) = crate::components::avatar::cached() {
+        req_tx.try_send(Req::SetAvatar(avatar)).unwrap_or_default();
+    }
 
-                        }
-                        Err(_e) => {
+    let diagnostics = Rc::new(RefCell::new(Diagnostics::default()));
+    // capabilities shared with the server, `None` until it answers the handshake
+    let capabilities = Rc::new(Cell::new(None::<u32>));
+
+    let diag = diagnostics.clone();
+    let caps = capabilities.clone();
+    spawn_local(async move {
+        let mut recorder = RecorderAgent::dispatcher();
+        while let Some(req) = req_rx.next().await {
+            let req = handshake::downgrade(req, caps.get().unwrap_or(0));
+            if !matches!(req, Req::Ping{..}|Req::Hello{..}) {
+                recorder.send(RecorderInput::Outgoing(req.clone()));
+            }
+            match tx.send(req).await {
+                Ok(()) => diag.borrow_mut().sent += 1,
+                Err(e) => report(&mut diag.borrow_mut(), e),
+            }
+        }
+    });
 
+    let mut ping_tx = req_tx.clone();
+    spawn_local(async move {
+        let mut dispatcher =  agent::WsRespAgent::dispatcher();
+        let mut status = WsStatusAgent::dispatcher();
+        let mut console = ConsoleAgent::dispatcher();
+        let mut recorder = RecorderAgent::dispatcher();
+        let mut heartbeat = Heartbeat::new();
+        let mut ticks = IntervalStream::new(HEARTBEAT_INTERVAL).fuse();
+        let mut rx = rx.fuse();
+        let mut unanswered_warned = false;
+        loop {
+            select! {
+                msg = rx.next() => {
+                    if let Some(Ok(_)) = &msg {
+                        diagnostics.borrow_mut().received += 1;
+                    }
+                    match msg {
+                        Some(Ok(Resp::Hello { version, capabilities: theirs })) => {
+                            match handshake::check(version) {
+                                Ok(()) => console.send(ItemKind::GameState{msg: format!("协议版本v{}", version)}),
+                                Err(msg) => console.send(ItemKind::Warn{msg}),
+                            }
+                            if ours & CAP_JSON != 0 && theirs & CAP_JSON == 0 {
+                                console.send(ItemKind::Warn{msg: "服务器不支持json格式，继续使用bincode".to_string()});
+                            }
+                            capabilities.set(Some(theirs & ours));
+                            recorder.send(RecorderInput::Capabilities(theirs));
+                        },
+                        Some(Ok(Resp::Pong { stamp })) => status.send(WsStatus::Latency(heartbeat.pong(stamp))),
+                        Some(Ok(resp)) => {
+                            recorder.send(RecorderInput::Incoming(resp.clone()));
+                            dispatcher.send(resp);
+                        },
+                        Some(Err(e)) => {
+                            let first = diagnostics.borrow().decode_errors == 0;
+                            let decode = matches!(e, TransportError::Decode{..});
+                            report(&mut diagnostics.borrow_mut(), e);
+                            if first && decode {
+                                console.send(ItemKind::Warn{msg: "无法解析服务器的消息，客户端和服务器的协议可能不一致，输入/diag查看详情".to_string()});
+                            }
+                            status.send(WsStatus::Diagnostics(diagnostics.borrow().clone()));
+                        },
+                        None => break
+                    }
+                },
+                _ = ticks.next() => {
+                    status.send(WsStatus::Diagnostics(diagnostics.borrow().clone()));
+                    match capabilities.get() {
+                        Some(caps) if caps & CAP_HEARTBEAT != 0 => match heartbeat.tick() {
+                            Beat::Ping(stamp) => ping_tx.send(Req::Ping { stamp }).await.unwrap_or_default(),
+                            Beat::Wait => {},
+                            // missed too many pongs, treat it as a disconnect
+                            Beat::Dead => break
+                        },
+                        Some(_) => {},
+                        None => if !unanswered_warned {
+                            // servers from before the handshake ignore it
+                            console.send(ItemKind::Warn{msg: "服务器没有回应版本握手，服务器版本可能过旧".to_string()});
+                            unanswered_warned = true;
                         }
                     }
                 }
-            });
-            console.send(ItemKind::GameState{msg: format!("已连接至服务器{}", server)});
-            return Some(req_tx)
-        } else {
-            console.send(ItemKind::Warn{msg: format!("无法连接至服务器{}", server)});
-            return None;
+            }
         }
-    }}}}    
-    console.send(ItemKind::Warn{msg: "url缺少参数server".to_string()});
-    None
-    
+        // closing the request channel ends the writer, which drops the connection
+        ping_tx.close_channel();
+        status.send(WsStatus::Diagnostics(diagnostics.borrow().clone()));
+        status.send(WsStatus::Disconnected);
+        console.send(ItemKind::Warn{msg: "连接已断开!".to_string()});
+    });
+    req_tx
+}
+
+/// count an error of the transport and log it to the browser console
+fn report(diagnostics: &mut Diagnostics, e: TransportError) {
+    match e {
+        TransportError::Encode => {
+            info!("failed to encode a request");
+            diagnostics.encode_errors += 1;
+        },
+        TransportError::Decode { size, reason } => {
+            info!("failed to decode a message of {} bytes: {}", size, reason);
+            diagnostics.decode_errors += 1;
+            diagnostics.last_decode_error = Some((size, reason));
+        },
+        TransportError::Socket(e) => {
+            info!("connection error: {}", e);
+            diagnostics.socket_errors += 1;
+            diagnostics.last_socket_error = Some(e);
+        },
+    }
 }
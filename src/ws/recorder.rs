@@ -0,0 +1,129 @@
+use serde::{Serialize, Deserialize};
+use yew_agent::{Agent, AgentLink, Context, HandlerId, Dispatched};
+
+use super::{Req, Resp};
+use crate::{components::console::{agent::ConsoleAgent, item::ItemKind}, utils::download};
+
+/// everything that went over the connection, as saved by `/record save`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Session {
+    /// protocol version of the client which recorded it
+    pub version: u16,
+    /// what the server announced in its handshake, missing from older recordings
+    #[serde(default)]
+    pub capabilities: u32,
+    pub entries: Vec<Entry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Entry {
+    /// ms since the recording started
+    pub at: u32,
+    pub event: Event,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum Event {
+    Incoming(Resp),
+    Outgoing(Req),
+}
+
+pub enum RecorderInput {
+    Incoming(Resp),
+    Outgoing(Req),
+    /// the capabilities the server announced
+    Capabilities(u32),
+    /// `/record start`, forget the session so far and record from now on
+    Start,
+    /// `/record stop`, the session stays until it is saved or cleared
+    Stop,
+    /// download the session so far
+    Save,
+    /// forget the session so far and start over
+    Clear,
+}
+
+/// timestamps the game messages of the connection once `/record start` asks for it,
+/// handshake and heartbeat are left out
+pub struct RecorderAgent {
+    _link: AgentLink<Self>,
+    recording: bool,
+    start: f64,
+    capabilities: u32,
+    entries: Vec<Entry>,
+}
+
+impl RecorderAgent {
+    fn push(&mut self, event: Event) {
+        if self.recording {
+            let at = (js_sys::Date::now() - self.start) as u32;
+            self.entries.push(Entry{at, event});
+        }
+    }
+
+    fn save(&mut self) -> Option<()> {
+        let session = Session {
+            version: dng_protocol::PROTOCOL_VERSION,
+            capabilities: self.capabilities,
+            entries: std::mem::take(&mut self.entries),
+        };
+        let json = serde_json::to_string(&session);
+        self.entries = session.entries;
+        let filename = format!("dng-session-{}.json", js_sys::Date::now() as u64);
+        download(filename.as_str(), json.ok()?.as_str(), "application/json")
+    }
+}
+
+impl Agent for RecorderAgent {
+    type Reach = Context<Self>;
+    type Message = ();
+    type Input = RecorderInput;
+    type Output = ();
+
+    fn create(link: AgentLink<Self>) -> Self {
+        Self {
+            _link: link,
+            recording: false,
+            start: js_sys::Date::now(),
+            capabilities: 0,
+            entries: Vec::new(),
+        }
+    }
+
+    fn update(&mut self, _msg: Self::Message) {
+        
+    }
+
+    fn handle_input(&mut self, msg: Self::Input, _id: HandlerId) {
+        match msg {
+            RecorderInput::Incoming(resp) => self.push(Event::Incoming(resp)),
+            RecorderInput::Outgoing(req) => self.push(Event::Outgoing(req)),
+            RecorderInput::Capabilities(capabilities) => self.capabilities = capabilities,
+            RecorderInput::Start => {
+                self.recording = true;
+                self.start = js_sys::Date::now();
+                self.entries.clear();
+            },
+            RecorderInput::Stop => self.recording = false,
+            RecorderInput::Save => {
+                let mut console = ConsoleAgent::dispatcher();
+                match self.save() {
+                    Some(_) => console.send(ItemKind::GameState{msg: format!("已保存{}条记录", self.entries.len())}),
+                    None => console.send(ItemKind::Warn{msg: "无法保存记录".to_string()}),
+                }
+            },
+            RecorderInput::Clear => {
+                self.start = js_sys::Date::now();
+                self.entries.clear();
+            },
+        }
+    }
+
+    fn connected(&mut self, _id: HandlerId) {
+        
+    }
+
+    fn disconnected(&mut self, _id: HandlerId) {
+
+    }
+}
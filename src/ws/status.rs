@@ -0,0 +1,65 @@
+use yew_agent::{Agent, AgentLink, Context, HandlerId};
+
+use std::collections::HashSet;
+
+/// state of the connection itself, as opposed to the game messages of `WsRespAgent`
+#[derive(Debug, Clone, PartialEq)]
+pub enum WsStatus {
+    /// round trip time of the last ping, in ms
+    Latency(u32),
+    Disconnected,
+    /// sent with every heartbeat tick and whenever something goes wrong
+    Diagnostics(Diagnostics),
+}
+
+/// counters of the connection, shown by `/diag`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Diagnostics {
+    pub sent: u32,
+    pub received: u32,
+    pub encode_errors: u32,
+    pub decode_errors: u32,
+    pub socket_errors: u32,
+    /// size and reason of the last message that could not be decoded
+    pub last_decode_error: Option<(usize, String)>,
+    pub last_socket_error: Option<String>,
+}
+
+pub struct WsStatusAgent {
+    link: AgentLink<Self>,
+    subscribers: HashSet<HandlerId>,
+}
+
+impl Agent for WsStatusAgent {
+    type Reach = Context<Self>;
+    type Message = ();
+    type Input = WsStatus;
+    type Output = WsStatus;
+
+    fn create(link: AgentLink<Self>) -> Self {
+        Self {
+            link,
+            subscribers: HashSet::new(),
+        }
+    }
+
+    fn update(&mut self, _msg: Self::Message) {
+        
+    }
+
+    fn handle_input(&mut self, msg: Self::Input, _id: HandlerId) {
+        for sub in &self.subscribers {
+            if sub.is_respondable() {
+                self.link.respond(*sub, msg.clone());
+            }
+        }
+    }
+
+    fn connected(&mut self, id: HandlerId) {
+        self.subscribers.insert(id);
+    }
+
+    fn disconnected(&mut self, id: HandlerId) {
+        self.subscribers.remove(&id);
+    }
+}
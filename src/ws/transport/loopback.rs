@@ -0,0 +1,38 @@
+use futures::{channel::mpsc::{unbounded, UnboundedSender, UnboundedReceiver}, SinkExt, StreamExt};
+
+use super::{Transport, TransportError, ReqSink, RespStream};
+use crate::ws::{Req, Resp};
+
+/// client end of an in-memory connection
+pub struct Loopback {
+    req_tx: UnboundedSender<Req>,
+    resp_rx: UnboundedReceiver<Resp>,
+}
+
+/// server end of an in-memory connection
+pub struct LoopbackServer {
+    req_rx: UnboundedReceiver<Req>,
+    resp_tx: UnboundedSender<Resp>,
+}
+
+/// create a connected pair, everything the client sends arrives at the server and vice versa
+pub fn loopback() -> (Loopback, LoopbackServer) {
+    let (req_tx, req_rx) = unbounded();
+    let (resp_tx, resp_rx) = unbounded();
+    (Loopback{req_tx, resp_rx}, LoopbackServer{req_rx, resp_tx})
+}
+
+impl Transport for Loopback {
+    fn split(self) -> (ReqSink, RespStream) {
+        let sink = self.req_tx.sink_map_err(|e|TransportError::Socket(e.to_string()));
+        let stream = self.resp_rx.map(Ok);
+        (Box::pin(sink), Box::pin(stream))
+    }
+}
+
+impl LoopbackServer {
+    /// requests of the client, and the way back to it
+    pub fn split(self) -> (UnboundedReceiver<Req>, UnboundedSender<Resp>) {
+        (self.req_rx, self.resp_tx)
+    }
+}
@@ -0,0 +1,46 @@
+use std::pin::Pin;
+use futures::{Sink, Stream};
+
+use super::{Req, Resp};
+
+mod websocket;
+mod loopback;
+mod script;
+mod netsim;
+mod replay;
+
+pub use websocket::{WebSocketTransport, WireFormat};
+pub use loopback::{loopback, LoopbackServer};
+pub use script::ScriptedServer;
+pub use netsim::{NetCondition, Simulated};
+pub use replay::Replay;
+
+#[derive(Debug)]
+pub enum TransportError {
+    /// a request could not be encoded
+    Encode,
+    /// a message arrived but could not be decoded
+    Decode {
+        /// length of the frame, in bytes
+        size: usize,
+        reason: String,
+    },
+    /// the connection is broken or closed
+    Socket(String),
+}
+
+pub type ReqSink = Pin<Box<dyn Sink<Req, Error = TransportError>>>;
+pub type RespStream = Pin<Box<dyn Stream<Item = Result<Resp, TransportError>>>>;
+
+/// a connection to a server which exchanges `PlayerRequest` and `PlayerResponse`
+///
+/// the ws service only ever talks to this trait, so the game can run against a real
+/// socket as well as an in-memory server
+pub trait Transport {
+    fn split(self) -> (ReqSink, RespStream);
+
+    /// capabilities this transport adds to the handshake
+    fn capabilities(&self) -> u32 {
+        0
+    }
+}
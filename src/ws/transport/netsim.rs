@@ -0,0 +1,108 @@
+use futures::{channel::mpsc::{unbounded, UnboundedReceiver}, SinkExt, Stream, StreamExt};
+use gloo_timers::future::TimeoutFuture;
+use js_sys::{Date, Math::random};
+use wasm_bindgen_futures::spawn_local;
+
+use super::{Transport, TransportError, ReqSink, RespStream};
+use crate::ws::Req;
+
+/// how bad the simulated network is
+///
+/// parsed from the `netsim` url parameter, e.g. `?netsim=latency:200,jitter:50,drop:0.05,reorder:0.1`,
+/// keys left out are zero
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct NetCondition {
+    /// ms added to every message, each way
+    pub latency: u32,
+    /// up to this many ms added on top of `latency`, at random
+    pub jitter: u32,
+    /// chance of a message being lost
+    pub drop: f64,
+    /// chance of a message being held back until later ones overtake it
+    pub reorder: f64,
+}
+
+impl NetCondition {
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut cond = Self::default();
+        for pair in s.split(',').filter(|p|!p.is_empty()) {
+            let (key, value) = pair.split_once(':')?;
+            match key.trim() {
+                "latency" => cond.latency = value.trim().parse().ok()?,
+                "jitter" => cond.jitter = value.trim().parse().ok()?,
+                "drop" => cond.drop = value.trim().parse().ok()?,
+                "reorder" => cond.reorder = value.trim().parse().ok()?,
+                _ => return None
+            }
+        }
+        Some(cond)
+    }
+}
+
+impl std::fmt::Display for NetCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "latency {}ms, jitter {}ms, drop {}%, reorder {}%", 
+            self.latency, self.jitter, self.drop * 100.0, self.reorder * 100.0)
+    }
+}
+
+/// wraps a transport, passing both directions through a simulated bad network
+pub struct Simulated<T> {
+    inner: T,
+    cond: NetCondition,
+}
+
+impl<T: Transport> Simulated<T> {
+    pub fn new(inner: T, cond: NetCondition) -> Self {
+        Self { inner, cond }
+    }
+}
+
+impl<T: Transport> Transport for Simulated<T> {
+    fn split(self) -> (ReqSink, RespStream) {
+        let (sink, stream) = self.inner.split();
+        let (req_tx, req_rx) = unbounded::<Req>();
+        let outgoing = delayed(req_rx, self.cond).map(Ok).forward(sink);
+        spawn_local(async move {
+            outgoing.await.unwrap_or_default();
+        });
+        let sink = req_tx.sink_map_err(|e|TransportError::Socket(e.to_string()));
+        (Box::pin(sink), Box::pin(delayed(stream, self.cond)))
+    }
+
+    fn capabilities(&self) -> u32 {
+        self.inner.capabilities()
+    }
+}
+
+/// pass every item of `input` on after a random delay, losing and reordering some of them
+///
+/// items which are not picked for reordering never overtake each other
+fn delayed<S>(input: S, cond: NetCondition) -> UnboundedReceiver<S::Item> 
+where S: Stream + 'static, S::Item: 'static
+{
+    let (tx, rx) = unbounded();
+    spawn_local(async move {
+        let mut input = Box::pin(input);
+        let mut last = 0.0f64;
+        while let Some(item) = input.next().await {
+            if random() < cond.drop {
+                continue;
+            }
+            let now = Date::now();
+            let mut at = now + cond.latency as f64 + random() * cond.jitter as f64;
+            if random() < cond.reorder {
+                at += (cond.latency + cond.jitter) as f64 + 50.0;
+            } else {
+                at = at.max(last);
+                last = at;
+            }
+            let tx = tx.clone();
+            spawn_local(async move {
+                TimeoutFuture::new((at - Date::now()).max(0.0) as u32).await;
+                tx.unbounded_send(item).unwrap_or_default();
+            });
+        }
+    });
+    rx
+}
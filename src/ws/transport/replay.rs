@@ -0,0 +1,66 @@
+use futures::{channel::mpsc::unbounded, sink, SinkExt};
+use gloo_net::http::Request;
+use gloo_timers::future::TimeoutFuture;
+use js_sys::Date;
+use wasm_bindgen_futures::spawn_local;
+
+use super::{Transport, TransportError, ReqSink, RespStream};
+use dng_protocol::CAP_HEARTBEAT;
+use crate::ws::{Resp, recorder::{Session, Event}};
+
+/// plays back the responses of a recorded session at their original pace, no server involved
+///
+/// the handshake is answered with the version of the client which made the recording
+/// and the capabilities of the server it talked to
+pub struct Replay {
+    url: String
+}
+
+impl Replay {
+    pub fn new(url: &str) -> Self {
+        Self { url: url.to_string() }
+    }
+}
+
+impl Transport for Replay {
+    fn split(self) -> (ReqSink, RespStream) {
+        let (tx, rx) = unbounded();
+        spawn_local(async move {
+            let session = match fetch(self.url.as_str()).await {
+                Ok(session) => session,
+                Err(e) => {
+                    tx.unbounded_send(Err(TransportError::Socket(e))).unwrap_or_default();
+                    return;
+                }
+            };
+            // nobody answers pings here, so the heartbeat would call it a disconnect
+            let hello = Resp::Hello { version: session.version, capabilities: session.capabilities & !CAP_HEARTBEAT };
+            tx.unbounded_send(Ok(hello)).unwrap_or_default();
+            let start = Date::now();
+            for entry in session.entries {
+                if let Event::Incoming(resp) = entry.event {
+                    let wait = start + entry.at as f64 - Date::now();
+                    if wait > 0.0 {
+                        TimeoutFuture::new(wait as u32).await;
+                    }
+                    if tx.unbounded_send(Ok(resp)).is_err() {
+                        return;
+                    }
+                }
+            }
+            tx.unbounded_send(Ok(Resp::Notice { msg: "回放结束".into() })).unwrap_or_default();
+        });
+        // requests go nowhere
+        let sink = sink::drain().sink_map_err(|e|match e {});
+        (Box::pin(sink), Box::pin(rx))
+    }
+}
+
+async fn fetch(url: &str) -> Result<Session, String> {
+    let resp = Request::get(url).send().await.map_err(|e|e.to_string())?;
+    if !resp.ok() {
+        return Err(format!("{} {}", resp.status(), resp.status_text()));
+    }
+    let text = resp.text().await.map_err(|e|e.to_string())?;
+    serde_json::from_str(text.as_str()).map_err(|e|e.to_string())
+}
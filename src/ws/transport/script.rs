@@ -0,0 +1,228 @@
+use bincode::serialize;
+use std::future::Future;
+
+use futures::{channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender}, StreamExt};
+use gloo_timers::future::TimeoutFuture;
+use wasm_bindgen_futures::spawn_local;
+
+use super::LoopbackServer;
+use crate::components::drawpad::{Color, Frame, Instruction, Tool};
+use dng_protocol::{HintSlot, RoomConfig, Scoring, GameMode};
+use crate::ws::{Req, Resp, PlayerState, PROTOCOL_VERSION, CAPABILITIES};
+
+pub enum Step {
+    /// push a response to the client
+    Send(Resp),
+    /// pause the script, in ms
+    Sleep(u32),
+    /// hold the script until the client sends a matching request
+    Expect(fn(&Req) -> bool),
+}
+
+/// a fake server which plays a fixed list of steps over a loopback connection
+///
+/// the handshake and pings are answered on the side, so a script may sleep as long as it likes
+pub struct ScriptedServer {
+    steps: Vec<Step>
+}
+
+impl ScriptedServer {
+    pub fn new(steps: Vec<Step>) -> Self {
+        Self { steps }
+    }
+
+    pub async fn run(self, server: LoopbackServer) {
+        let (mut req_rx, resp_tx) = server.split();
+        let (expect_tx, expect_rx) = unbounded::<Req>();
+        let pong_tx = resp_tx.clone();
+        spawn_local(async move {
+            while let Some(req) = req_rx.next().await {
+                match req {
+                    Req::Ping { stamp } => pong_tx.unbounded_send(Resp::Pong { stamp }).unwrap_or_default(),
+                    Req::Hello { .. } => pong_tx.unbounded_send(Resp::Hello { 
+                        version: PROTOCOL_VERSION, 
+                        capabilities: CAPABILITIES
+                    }).unwrap_or_default(),
+                    req => expect_tx.unbounded_send(req).unwrap_or_default(),
+                }
+            }
+        });
+        self.play(resp_tx, expect_rx, TimeoutFuture::new).await
+    }
+
+    /// the steps alone, `reqs` are the requests of the client other than the handshake and pings
+    async fn play<F>(self, resp_tx: UnboundedSender<Resp>, mut reqs: UnboundedReceiver<Req>, sleep: impl Fn(u32) -> F)
+    where F: Future<Output = ()>
+    {
+        for step in self.steps {
+            match step {
+                Step::Send(resp) => resp_tx.unbounded_send(resp).unwrap_or_default(),
+                Step::Sleep(ms) => sleep(ms).await,
+                Step::Expect(matches) => loop {
+                    match reqs.next().await {
+                        Some(req) if matches(&req) => break,
+                        Some(_) => {},
+                        None => return,
+                    }
+                },
+            }
+        }
+    }
+
+    /// a short game of two players: bob draws, everyone marks, then it is our turn
+    pub fn demo() -> Self {
+        use Step::*;
+        let players = vec![player("bob", 0), player("me", 1)];
+        let mut steps = vec![
+            Send(Resp::Notice { msg: "scripted demo server".into() }),
+            Send(Resp::PlayerStates(players)),
+            Send(Resp::Seat(1)),
+            Send(Resp::Host(1)),
+            Send(Resp::RoomConfig(RoomConfig { rounds: 1, turn_time: 20, max_players: 8, scoring: Scoring::Votes, teams: 0, mode: GameMode::Classic })),
+            Sleep(1000),
+            Send(Resp::GameStart),
+            Send(Resp::TurnStart(0)),
+        ];
+        let strokes = [
+            [(40, 40), (88, 40), (88, 88)],
+            [(88, 88), (40, 88), (40, 40)],
+        ];
+        for (idx, cd) in (0..8u8).rev().enumerate() {
+            steps.push(Send(Resp::CountDown(cd)));
+            match cd {
+                7 => steps.push(Send(Resp::Hint { slots: HintSlot::mask("方块", &[]) })),
+                3 => steps.push(Send(Resp::Hint { slots: HintSlot::mask("方块", &[1]) })),
+                _ => {}
+            }
+            if let Some(stroke) = strokes.get(idx) {
+                steps.push(Send(chunk(stroke, Color::new(0xdd, 0x66, 0x66))));
+            }
+            steps.push(Sleep(1000));
+        }
+        steps.extend([
+            Send(Resp::Guessed { idx: 1, order: 1, elapsed: 6, points: 5 }),
+            Sleep(1000),
+            Send(Resp::TurnEnd),
+            Send(Resp::MarkStart),
+            Send(Resp::Poll),
+            Expect(|req|matches!(req, Req::Rate{..}|Req::Mark{..})),
+            Sleep(3000),
+            Send(Resp::MarkEnd),
+            Send(Resp::MarkResult { drawer: 0, stars: [0, 0, 1, 0, 1] }),
+            Send(Resp::TurnStart(1)),
+            Send(Resp::Topic { topic_word: "apple".into() }),
+        ]);
+        for cd in (0..20u8).rev() {
+            steps.push(Send(Resp::CountDown(cd)));
+            steps.push(Sleep(1000));
+        }
+        steps.extend([
+            Send(Resp::TurnEnd),
+            Send(Resp::GameEnd),
+        ]);
+        Self::new(steps)
+    }
+}
+
+fn player(name: &str, idx: u8) -> PlayerState {
+    PlayerState {
+        name: name.into(),
+        idx,
+        ready: true,
+        score: [0;3],
+        drawpoint: 0,
+        timepoint: 0,
+        team: None,
+    }
+}
+
+/// a chunk holding one pencil stroke, one point per frame
+fn chunk(stroke: &[(u8, u8)], color: Color) -> Resp {
+    let mut frames = vec![Frame{instructions: vec![
+        Instruction::SetColor(color),
+        Instruction::SetTool(Tool::Pencil),
+        Instruction::PointerDown(stroke[0]),
+    ]}];
+    for coor in &stroke[1..stroke.len()-1] {
+        frames.push(Frame{instructions: vec![Instruction::PointerMove(*coor)]});
+    }
+    frames.push(Frame{instructions: vec![Instruction::PointerUp(stroke[stroke.len()-1])]});
+    Resp::Chunk { bin: serialize(&frames).unwrap_or_default() }
+}
+
+#[cfg(test)]
+mod tests {
+    use bincode::deserialize;
+    use futures::{executor::block_on, future::ready, SinkExt};
+
+    use super::*;
+    use super::super::{loopback, Transport};
+    use crate::components::console::{item::ItemKind, item_of};
+    use crate::locals::ZH;
+    use crate::state::{reduce, GameState};
+    use dng_protocol::{FigureLocal, Stage};
+
+    /// what the client gets from the demo, when it rates the drawing of bob as soon as it is asked
+    fn demo() -> Vec<Resp> {
+        let (client, server) = loopback();
+        let (mut req_tx, resp_rx) = client.split();
+        block_on(req_tx.send(Req::Rate { stars: 4 })).unwrap();
+        let (req_rx, resp_tx) = server.split();
+        block_on(ScriptedServer::demo().play(resp_tx, req_rx, |_|ready(())));
+        block_on(resp_rx.map(Result::unwrap).collect())
+    }
+
+    #[test]
+    fn demo_plays_a_game() {
+        let resps = demo();
+        let mut state = GameState::default();
+        let mut drawers = Vec::new();
+        for resp in &resps {
+            state = reduce(state, resp);
+            if let Some(drawer) = state.drawer.filter(|d|drawers.last() != Some(d)) {
+                drawers.push(drawer);
+            }
+        }
+        let names: Vec<_> = state.players.iter().map(|p|p.name.as_str()).collect();
+        assert_eq!(names, ["bob", "me"]);
+        assert_eq!(state.me().map(|me|me.idx), Some(1));
+        assert!(state.is_host());
+        assert_eq!(drawers, [0, 1]);
+        assert_eq!(state.turns.len(), 2);
+        assert_eq!(state.stage, Stage::Over);
+    }
+
+    #[test]
+    fn demo_fills_the_console() {
+        let mut state = GameState::default();
+        let mut items = Vec::new();
+        for resp in demo() {
+            state = reduce(state, &resp);
+            items.extend(item_of(&resp, &ZH, &state, items.len()));
+        }
+        assert!(matches!(&items[0], ItemKind::Notice { msg } if msg == "scripted demo server"));
+        assert_eq!(items.iter().filter(|i|matches!(i, ItemKind::Poll{..})).count(), 1);
+        assert!(items.iter().any(|i|matches!(i, ItemKind::MarkResult { stars, .. } if *stars == [0, 0, 1, 0, 1])));
+        assert!(items.iter().any(|i|matches!(i, ItemKind::GameState { msg } if msg.ends_with("apple"))));
+        assert!(matches!(items.last(), Some(ItemKind::GameState { msg }) if msg == ZH.game_end));
+    }
+
+    #[test]
+    fn demo_draws_a_square() {
+        let mut figure = FigureLocal::blank();
+        for resp in demo() {
+            if let Resp::Chunk { bin } = resp {
+                for frame in deserialize::<Vec<Frame>>(&bin).unwrap() {
+                    figure.render_frame(&frame);
+                }
+            }
+        }
+        let red = Color::new(0xdd, 0x66, 0x66);
+        let data = figure.get_ref();
+        let at = |x: usize, y: usize| data[x][y];
+        for i in 40..=88 {
+            assert_eq!([at(i, 40), at(88, i), at(i, 88), at(40, i)], [red; 4]);
+        }
+        assert_eq!(at(64, 64), Color::white());
+    }
+}
@@ -0,0 +1,93 @@
+use std::{cell::Cell, rc::Rc};
+
+use bincode::{serialize, deserialize};
+use dng_protocol::CAP_JSON;
+use futures::{future::ready, SinkExt, StreamExt};
+use gloo_net::websocket::{Message, futures::WebSocket};
+
+use super::{Transport, TransportError, ReqSink, RespStream};
+use crate::ws::{Req, Resp};
+
+/// how requests are encoded, responses are always decoded by the kind of frame they arrive in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WireFormat {
+    /// binary frames, what dng-server speaks by default
+    Bincode,
+    /// text frames, readable in devtools, chunks stay binary
+    Json,
+}
+
+/// a websocket to dng-server
+///
+/// starts out with bincode, and switches to json once the server accepts it in the handshake
+pub struct WebSocketTransport {
+    ws: WebSocket,
+    format: WireFormat,
+}
+
+impl WebSocketTransport {
+    pub fn open(url: &str, format: WireFormat) -> Result<Self, TransportError> {
+        WebSocket::open(url)
+            .map(|ws|Self{ws, format})
+            .map_err(|e|TransportError::Socket(e.to_string()))
+    }
+}
+
+impl Transport for WebSocketTransport {
+    fn split(self) -> (ReqSink, RespStream) {
+        let (ws_tx, ws_rx) = self.ws.split();
+        let active = Rc::new(Cell::new(WireFormat::Bincode));
+        let requested = self.format;
+
+        let format = active.clone();
+        let sink = ws_tx
+            .sink_map_err(|e|TransportError::Socket(e.to_string()))
+            .with(move |req: Req| ready(encode(&req, format.get())));
+        let stream = ws_rx.filter_map(move |msg| ready(
+            match msg {
+                Ok(msg) => {
+                    let resp = decode(msg);
+                    if let Ok(Resp::Hello { capabilities, .. }) = &resp {
+                        if requested == WireFormat::Json && capabilities & CAP_JSON != 0 {
+                            active.set(WireFormat::Json);
+                        }
+                    }
+                    Some(resp)
+                },
+                Err(e) => Some(Err(TransportError::Socket(e.to_string()))),
+            }
+        ));
+        (Box::pin(sink), Box::pin(stream))
+    }
+
+    fn capabilities(&self) -> u32 {
+        match self.format {
+            WireFormat::Bincode => 0,
+            WireFormat::Json => CAP_JSON,
+        }
+    }
+}
+
+fn encode(req: &Req, format: WireFormat) -> Result<Message, TransportError> {
+    match (format, req) {
+        (WireFormat::Json, Req::Chunk{..}) | (WireFormat::Bincode, _) => {
+            serialize(req).map(Message::Bytes).map_err(|_|TransportError::Encode)
+        }
+        (WireFormat::Json, _) => {
+            serde_json::to_string(req).map(Message::Text).map_err(|_|TransportError::Encode)
+        }
+    }
+}
+
+fn decode(msg: Message) -> Result<Resp, TransportError> {
+    match msg {
+        Message::Bytes(bin) => deserialize::<Resp>(&bin).map_err(|e|TransportError::Decode {
+            size: bin.len(),
+            reason: e.to_string(),
+        }),
+        Message::Text(text) => serde_json::from_str::<Resp>(&text).map_err(|e|TransportError::Decode {
+            size: text.len(),
+            reason: e.to_string(),
+        }),
+    }
+}